@@ -0,0 +1,42 @@
+/// Common regex constructs, as `(construct, description)`.
+///
+/// The constructs are inserted as-is, so placeholders such as `...` are left
+/// for the user to replace.
+pub const CHEATSHEET: &[(&str, &str)] = &[
+    (".", "Any character except a new line"),
+    (r"\d", "A digit"),
+    (r"\D", "Anything but a digit"),
+    (r"\w", "A word character: a letter, digit or underscore"),
+    (r"\W", "Anything but a word character"),
+    (r"\s", "A whitespace, such as a space, tab or new line"),
+    (r"\S", "Anything but a whitespace"),
+    (r"\b", "A word boundary"),
+    (
+        "^",
+        "The start of the text, or of the line in multiline mode",
+    ),
+    ("$", "The end of the text, or of the line in multiline mode"),
+    ("[...]", "Any of the characters in the brackets"),
+    ("[^...]", "Any character that is not in the brackets"),
+    ("[a-z]", "Any character in the range"),
+    ("*", "Zero or more of the previous item"),
+    ("+", "One or more of the previous item"),
+    ("?", "Zero or one of the previous item"),
+    ("{m,n}", "Between m and n of the previous item"),
+    (
+        "*?",
+        "Zero or more of the previous item, as few as possible",
+    ),
+    ("a|b", "Either a or b"),
+    (
+        "(...)",
+        "A capture group, referenced as $1 in the replacement",
+    ),
+    ("(?:...)", "A group that doesn't capture"),
+    (
+        "(?P<name>...)",
+        "A named capture group, referenced as ${name} in the replacement",
+    ),
+    (r"\p{Han}", "A character of the Unicode script or class"),
+    ("(?i)", "Case insensitive from here on"),
+];
@@ -0,0 +1,72 @@
+use crate::step::RegexFlags;
+use regex_syntax::hir::{self, Hir, HirKind};
+
+/// Lengths of the inputs that the regex is timed against.
+const INPUT_LENGTHS: &[usize] = &[10, 100, 1000, 10000];
+
+/// How long, in milliseconds, each input length is at least timed for, so
+/// that the clock resolution doesn't dominate short match times.
+const MIN_SAMPLE_MS: f64 = 5.0;
+
+/// Whether the match time may grow super-linearly with the input length,
+/// which would make the regex vulnerable to denial of service (ReDoS).
+///
+/// The regex is timed against inputs of increasing lengths that repeat a
+/// character matched by the start of the pattern. Is `None` if no such
+/// character could be found.
+pub fn has_redos_risk(re: &regex::Regex, flags: RegexFlags) -> Option<bool> {
+    let hir = flags.parser().parse(re.as_str()).ok()?;
+    let c = first_char(&hir)?;
+
+    let times: Vec<f64> = INPUT_LENGTHS
+        .iter()
+        .map(|len| {
+            // the trailing character tries to make the match fail at the end
+            let mut input: String = std::iter::repeat(c).take(*len).collect();
+            input.push('\u{1}');
+            time_match(re, &input)
+        })
+        .collect();
+
+    // a linear growth keeps the time ratio close to the length ratio, while a
+    // quadratic one would square it; the threshold lies in between
+    let risk = INPUT_LENGTHS
+        .windows(2)
+        .zip(times.windows(2))
+        .any(|(lengths, times)| {
+            let length_ratio = lengths[1] as f64 / lengths[0] as f64;
+            times[0] > 0.0 && times[1] / times[0] > length_ratio.powf(1.5)
+        });
+    Some(risk)
+}
+
+/// Average time, in milliseconds, of matching the regex against the input.
+fn time_match(re: &regex::Regex, input: &str) -> f64 {
+    let start = js_sys::Date::now();
+    let mut iterations = 0;
+    loop {
+        re.is_match(input);
+        iterations += 1;
+        let elapsed = js_sys::Date::now() - start;
+        if elapsed >= MIN_SAMPLE_MS {
+            return elapsed / iterations as f64;
+        }
+    }
+}
+
+/// A character that can be matched by the start of the pattern.
+fn first_char(hir: &Hir) -> Option<char> {
+    match hir.kind() {
+        HirKind::Literal(hir::Literal::Unicode(c)) => Some(*c),
+        HirKind::Literal(hir::Literal::Byte(b)) => Some(*b as char),
+        HirKind::Class(hir::Class::Unicode(class)) => class.iter().next().map(|r| r.start()),
+        HirKind::Class(hir::Class::Bytes(class)) => class.iter().next().map(|r| r.start() as char),
+        HirKind::Repetition(repetition) => first_char(&repetition.hir),
+        HirKind::Group(group) => first_char(&group.hir),
+        // anchors and empty elements don't consume characters, so the
+        // following element is tried
+        HirKind::Concat(hirs) => hirs.iter().find_map(first_char),
+        HirKind::Alternation(hirs) => hirs.iter().find_map(first_char),
+        HirKind::Empty | HirKind::Anchor(_) | HirKind::WordBoundary(_) => None,
+    }
+}
@@ -0,0 +1,451 @@
+use crate::step::RegexFlags;
+use regex_syntax::ast::{
+    self, Assertion, AssertionKind, Ast, Class, ClassPerlKind, GroupKind, RepetitionKind,
+    RepetitionRange,
+};
+use yew::prelude::*;
+
+/// Approximate width of a monospace character in the diagram.
+const CHAR_WIDTH: f64 = 8.0;
+/// Height of the boxes.
+const BOX_HEIGHT: f64 = 24.0;
+/// Horizontal space between consecutive elements.
+const GAP: f64 = 10.0;
+/// Radius of the curves that connect branches and loops.
+const ARC: f64 = 10.0;
+/// Vertical space between stacked branches.
+const VGAP: f64 = 10.0;
+/// Height reserved for group and repetition labels.
+const LABEL_HEIGHT: f64 = 14.0;
+
+/// A railroad diagram element.
+///
+/// Every element is drawn around a horizontal line, the "track", and knows
+/// how far it extends above (`up`) and below (`down`) it.
+enum Diagram {
+    /// Text that is matched as-is.
+    Terminal(String),
+    /// Text that describes what is matched, such as classes or anchors.
+    NonTerminal(String),
+    Sequence(Vec<Diagram>),
+    Choice(Vec<Diagram>),
+    Repeat {
+        item: Box<Diagram>,
+        optional: bool,
+        repeats: bool,
+        label: Option<String>,
+    },
+    Group {
+        item: Box<Diagram>,
+        label: String,
+    },
+}
+
+impl Diagram {
+    fn width(&self) -> f64 {
+        match self {
+            Diagram::Terminal(text) | Diagram::NonTerminal(text) => {
+                text.chars().count() as f64 * CHAR_WIDTH + 2.0 * GAP
+            }
+            Diagram::Sequence(items) if items.is_empty() => GAP,
+            Diagram::Sequence(items) => {
+                items.iter().map(Diagram::width).sum::<f64>() + GAP * (items.len() - 1) as f64
+            }
+            Diagram::Choice(items) => {
+                items.iter().map(Diagram::width).fold(0.0, f64::max) + 4.0 * ARC
+            }
+            Diagram::Repeat { item, .. } => item.width() + 4.0 * ARC,
+            Diagram::Group { item, label } => {
+                let label_width = label.chars().count() as f64 * CHAR_WIDTH;
+                item.width().max(label_width) + 2.0 * GAP
+            }
+        }
+    }
+
+    fn up(&self) -> f64 {
+        match self {
+            Diagram::Terminal(_) | Diagram::NonTerminal(_) => BOX_HEIGHT / 2.0,
+            Diagram::Sequence(items) => items.iter().map(Diagram::up).fold(0.0, f64::max),
+            Diagram::Choice(items) => items.first().map(Diagram::up).unwrap_or_default(),
+            Diagram::Repeat { item, optional, .. } => {
+                if *optional {
+                    skip_offset(item)
+                } else {
+                    item.up()
+                }
+            }
+            Diagram::Group { item, .. } => item.up() + LABEL_HEIGHT,
+        }
+    }
+
+    fn down(&self) -> f64 {
+        match self {
+            Diagram::Terminal(_) | Diagram::NonTerminal(_) => BOX_HEIGHT / 2.0,
+            Diagram::Sequence(items) => items.iter().map(Diagram::down).fold(0.0, f64::max),
+            Diagram::Choice(items) => match (branch_offsets(items).last(), items.last()) {
+                (Some(offset), Some(last)) => offset + last.down(),
+                _ => 0.0,
+            },
+            Diagram::Repeat {
+                item,
+                repeats,
+                label,
+                ..
+            } => {
+                if *repeats {
+                    let label_height = if label.is_some() { LABEL_HEIGHT } else { 0.0 };
+                    loop_offset(item) + label_height
+                } else {
+                    item.down()
+                }
+            }
+            Diagram::Group { item, .. } => item.down() + GAP / 2.0,
+        }
+    }
+
+    /// Draws the element with its track starting at `(x, y)`.
+    fn render(&self, x: f64, y: f64) -> Html {
+        let w = self.width();
+        match self {
+            Diagram::Terminal(text) | Diagram::NonTerminal(text) => {
+                let (class, rx) = match self {
+                    Diagram::Terminal(_) => ("rr-terminal", BOX_HEIGHT / 2.0),
+                    _ => ("rr-nonterminal", 0.0),
+                };
+                html! {
+                    <g class={class}>
+                        <rect
+                            x={x.to_string()}
+                            y={(y - BOX_HEIGHT / 2.0).to_string()}
+                            width={w.to_string()}
+                            height={BOX_HEIGHT.to_string()}
+                            rx={rx.to_string()}
+                        />
+                        <text
+                            x={(x + w / 2.0).to_string()}
+                            y={(y + 4.0).to_string()}
+                            text-anchor="middle"
+                        >
+                            {text}
+                        </text>
+                    </g>
+                }
+            }
+            Diagram::Sequence(items) if items.is_empty() => line(x, y, x + GAP, y),
+            Diagram::Sequence(items) => {
+                let mut cx = x;
+                let mut parts = vec![];
+                for (i, item) in items.iter().enumerate() {
+                    let item_width = item.width();
+                    parts.push(item.render(cx, y));
+                    cx += item_width;
+                    if i + 1 != items.len() {
+                        parts.push(line(cx, y, cx + GAP, y));
+                        cx += GAP;
+                    }
+                }
+                html! { <g>{ for parts }</g> }
+            }
+            Diagram::Choice(items) => {
+                let inner_width = w - 4.0 * ARC;
+                let (left, right) = (x + 2.0 * ARC, x + 2.0 * ARC + inner_width);
+                let parts = items
+                    .iter()
+                    .zip(branch_offsets(items))
+                    .map(|(item, offset)| {
+                        let by = y + offset;
+                        let connectors = if offset == 0.0 {
+                            html! {
+                                <>
+                                {line(x, y, left, y)}
+                                {line(right, y, x + w, y)}
+                                </>
+                            }
+                        } else {
+                            html! {
+                                <>
+                                <path d={format!(
+                                    "M {} {} Q {} {} {} {} L {} {} Q {} {} {} {}",
+                                    x, y,
+                                    x + ARC, y, x + ARC, y + ARC,
+                                    x + ARC, by - ARC,
+                                    x + ARC, by, left, by,
+                                )} />
+                                <path d={format!(
+                                    "M {} {} Q {} {} {} {} L {} {} Q {} {} {} {}",
+                                    right, by,
+                                    right + ARC, by, right + ARC, by - ARC,
+                                    right + ARC, y + ARC,
+                                    right + ARC, y, x + w, y,
+                                )} />
+                                </>
+                            }
+                        };
+                        html! {
+                            <>
+                            {connectors}
+                            {item.render(left, by)}
+                            {line(left + item.width(), by, right, by)}
+                            </>
+                        }
+                    });
+                html! { <g>{ for parts }</g> }
+            }
+            Diagram::Repeat {
+                item,
+                optional,
+                repeats,
+                label,
+            } => {
+                let (left, right) = (x + 2.0 * ARC, x + w - 2.0 * ARC);
+                let skip = optional.then(|| {
+                    let sy = y - skip_offset(item);
+                    html! {
+                        <path d={format!(
+                            "M {} {} Q {} {} {} {} L {} {} Q {} {} {} {} L {} {} Q {} {} {} {} L {} {} Q {} {} {} {}",
+                            x, y,
+                            x + ARC, y, x + ARC, y - ARC,
+                            x + ARC, sy + ARC,
+                            x + ARC, sy, left, sy,
+                            right, sy,
+                            right + ARC, sy, right + ARC, sy + ARC,
+                            right + ARC, y - ARC,
+                            right + ARC, y, x + w, y,
+                        )} />
+                    }
+                });
+                let repetition = repeats.then(|| {
+                    let ly = y + loop_offset(item);
+                    html! {
+                        <>
+                        <path d={format!(
+                            "M {} {} Q {} {} {} {} L {} {} Q {} {} {} {} L {} {} Q {} {} {} {} L {} {} Q {} {} {} {}",
+                            right, y,
+                            right + ARC, y, right + ARC, y + ARC,
+                            right + ARC, ly - ARC,
+                            right + ARC, ly, right, ly,
+                            left, ly,
+                            left - ARC, ly, left - ARC, ly - ARC,
+                            left - ARC, y + ARC,
+                            left - ARC, y, left, y,
+                        )} />
+                        if let Some(label) = label {
+                            <text
+                                class="rr-label"
+                                x={(x + w / 2.0).to_string()}
+                                y={(ly + LABEL_HEIGHT - 2.0).to_string()}
+                                text-anchor="middle"
+                            >
+                                {label}
+                            </text>
+                        }
+                        </>
+                    }
+                });
+                html! {
+                    <g>
+                        {line(x, y, left, y)}
+                        {item.render(left, y)}
+                        {line(left + item.width(), y, x + w, y)}
+                        {skip.unwrap_or_default()}
+                        {repetition.unwrap_or_default()}
+                    </g>
+                }
+            }
+            Diagram::Group { item, label } => {
+                let top = y - item.up() - LABEL_HEIGHT;
+                html! {
+                    <g>
+                        <rect
+                            class="rr-group"
+                            x={(x + GAP / 2.0).to_string()}
+                            y={top.to_string()}
+                            width={(w - GAP).to_string()}
+                            height={(self.up() + self.down()).to_string()}
+                        />
+                        <text
+                            class="rr-label"
+                            x={(x + GAP / 2.0 + 4.0).to_string()}
+                            y={(top + LABEL_HEIGHT - 3.0).to_string()}
+                        >
+                            {label}
+                        </text>
+                        {line(x, y, x + GAP, y)}
+                        {item.render(x + GAP, y)}
+                        {line(x + GAP + item.width(), y, x + w, y)}
+                    </g>
+                }
+            }
+        }
+    }
+}
+
+fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> Html {
+    html! {
+        <path d={format!("M {} {} L {} {}", x1, y1, x2, y2)} />
+    }
+}
+
+/// Vertical offset of each branch track of a choice, relative to the first.
+fn branch_offsets(items: &[Diagram]) -> Vec<f64> {
+    let mut offsets = vec![];
+    let mut offset = 0.0;
+    for (i, item) in items.iter().enumerate() {
+        if i != 0 {
+            // leaves enough room for the connecting curves
+            offset += (items[i - 1].down() + VGAP + item.up()).max(2.0 * ARC);
+        }
+        offsets.push(offset);
+    }
+    offsets
+}
+
+/// Distance from the track to the line that skips an optional item.
+fn skip_offset(item: &Diagram) -> f64 {
+    (item.up() + VGAP).max(2.0 * ARC)
+}
+
+/// Distance from the track to the line that loops back to a repeated item.
+fn loop_offset(item: &Diagram) -> f64 {
+    (item.down() + VGAP).max(2.0 * ARC)
+}
+
+/// Converts the parsed pattern into a diagram.
+///
+/// `pattern` is the text that was parsed, used to describe classes just as
+/// they were written.
+fn from_ast(pattern: &str, ast: &Ast) -> Diagram {
+    let source = |span: &ast::Span| pattern[span.start.offset..span.end.offset].to_string();
+    match ast {
+        Ast::Empty(_) => Diagram::Sequence(vec![]),
+        Ast::Flags(flags) => Diagram::NonTerminal(format!("flags {}", source(&flags.span))),
+        Ast::Literal(literal) => Diagram::Terminal(literal.c.to_string()),
+        Ast::Dot(_) => Diagram::NonTerminal("any character".into()),
+        Ast::Assertion(Assertion { kind, .. }) => Diagram::NonTerminal(
+            match kind {
+                AssertionKind::StartLine => "start of line",
+                AssertionKind::EndLine => "end of line",
+                AssertionKind::StartText => "start of text",
+                AssertionKind::EndText => "end of text",
+                AssertionKind::WordBoundary => "word boundary",
+                AssertionKind::NotWordBoundary => "not a word boundary",
+            }
+            .into(),
+        ),
+        Ast::Class(Class::Perl(class)) => {
+            let name = match class.kind {
+                ClassPerlKind::Digit => "digit",
+                ClassPerlKind::Space => "whitespace",
+                ClassPerlKind::Word => "word character",
+            };
+            if class.negated {
+                Diagram::NonTerminal(format!("not a {}", name))
+            } else {
+                Diagram::NonTerminal(name.into())
+            }
+        }
+        Ast::Class(class) => Diagram::NonTerminal(source(class.span())),
+        Ast::Repetition(repetition) => {
+            let item = Box::new(from_ast(pattern, &repetition.ast));
+            let lazy = if repetition.greedy { "" } else { ", lazy" };
+            let (optional, repeats, label) = match &repetition.op.kind {
+                RepetitionKind::ZeroOrOne => (true, false, None),
+                RepetitionKind::ZeroOrMore => (true, true, None),
+                RepetitionKind::OneOrMore => (false, true, None),
+                RepetitionKind::Range(RepetitionRange::Exactly(n)) => {
+                    (*n == 0, *n > 1, Some(format!("{} times", n)))
+                }
+                RepetitionKind::Range(RepetitionRange::AtLeast(n)) => {
+                    (*n == 0, true, Some(format!("{}+ times", n)))
+                }
+                RepetitionKind::Range(RepetitionRange::Bounded(n, m)) => {
+                    (*n == 0, *m > 1, Some(format!("{} to {} times", n, m)))
+                }
+            };
+            let label = match label {
+                Some(label) => Some(format!("{}{}", label, lazy)),
+                None if !repetition.greedy => Some("lazy".into()),
+                None => None,
+            };
+            Diagram::Repeat {
+                item,
+                optional,
+                repeats,
+                label,
+            }
+        }
+        Ast::Group(group) => {
+            let item = Box::new(from_ast(pattern, &group.ast));
+            match &group.kind {
+                GroupKind::CaptureIndex(i) => Diagram::Group {
+                    item,
+                    label: format!("group ${}", i),
+                },
+                GroupKind::CaptureName(name) => Diagram::Group {
+                    item,
+                    label: format!("group ${} \"{}\"", name.index, name.name),
+                },
+                GroupKind::NonCapturing(flags) if flags.items.is_empty() => *item,
+                GroupKind::NonCapturing(flags) => Diagram::Group {
+                    item,
+                    label: format!("flags {}", source(&flags.span)),
+                },
+            }
+        }
+        Ast::Alternation(alternation) => Diagram::Choice(
+            alternation
+                .asts
+                .iter()
+                .map(|ast| from_ast(pattern, ast))
+                .collect(),
+        ),
+        Ast::Concat(concat) => {
+            let mut items: Vec<Diagram> = vec![];
+            for ast in concat.asts.iter() {
+                match (items.last_mut(), from_ast(pattern, ast)) {
+                    // merges consecutive literals into a single box
+                    (Some(Diagram::Terminal(last)), Diagram::Terminal(text)) => {
+                        last.push_str(&text)
+                    }
+                    (_, item) => items.push(item),
+                }
+            }
+            Diagram::Sequence(items)
+        }
+    }
+}
+
+/// Draws the pattern as an SVG railroad diagram.
+///
+/// Only the verbose flag changes how the pattern is read, as whitespace and
+/// comments are left out of the diagram.
+///
+/// Returns the parse error if the pattern is invalid.
+pub fn regex_diagram(pattern: &str, flags: RegexFlags) -> Result<Html, String> {
+    let ast = ast::parse::ParserBuilder::new()
+        .ignore_whitespace(flags.verbose)
+        .build()
+        .parse(pattern)
+        .map_err(|e| e.to_string())?;
+    let diagram = from_ast(pattern, &ast);
+
+    // room for the start and end markers
+    let margin = 2.0 * GAP;
+    let width = diagram.width() + 2.0 * margin;
+    let height = diagram.up() + diagram.down() + 2.0 * GAP;
+    let y = diagram.up() + GAP;
+    Ok(html! {
+        <svg
+            class="railroad-diagram"
+            width={width.to_string()}
+            height={height.to_string()}
+            viewBox={format!("0 0 {} {}", width, height)}
+        >
+            <circle cx={(GAP / 2.0).to_string()} cy={y.to_string()} r="4" />
+            {line(GAP / 2.0, y, margin, y)}
+            {diagram.render(margin, y)}
+            {line(width - margin, y, width - GAP / 2.0, y)}
+            <circle cx={(width - GAP / 2.0).to_string()} cy={y.to_string()} r="4" />
+        </svg>
+    })
+}
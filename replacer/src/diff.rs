@@ -0,0 +1,59 @@
+use similar::{ChangeTag, TextDiff};
+use yew::prelude::*;
+
+/// How many bytes both texts can have together for their byte changes to be
+/// calculated, as the word diff gets slow on large texts.
+pub const MAX_DIFF_BYTES: usize = 200_000;
+
+/// The word-by-word changes that turn `a` into `b`, in order.
+pub fn word_changes<'a>(a: &'a str, b: &'a str) -> Vec<(ChangeTag, &'a str)> {
+    TextDiff::from_words(a, b)
+        .iter_all_changes()
+        .map(|change| (change.tag(), change.value()))
+        .collect()
+}
+
+/// How many bytes were inserted and deleted, word by word, to turn `a`
+/// into `b`.
+pub fn byte_changes(a: &str, b: &str) -> (usize, usize) {
+    word_changes(a, b)
+        .into_iter()
+        .fold((0, 0), |(inserted, deleted), (tag, text)| match tag {
+            ChangeTag::Equal => (inserted, deleted),
+            ChangeTag::Insert => (inserted + text.len(), deleted),
+            ChangeTag::Delete => (inserted, deleted + text.len()),
+        })
+}
+
+/// Renders both texts side by side, highlighting the words that were removed
+/// from `a` and the words that were inserted into `b`.
+pub fn render_side_by_side(a: &str, b: &str, a_title: &str, b_title: &str) -> Html {
+    let mut left = vec![];
+    let mut right = vec![];
+    for (tag, text) in word_changes(a, b) {
+        match tag {
+            ChangeTag::Equal => {
+                left.push(html! {{text}});
+                right.push(html! {{text}});
+            }
+            ChangeTag::Delete => left.push(html! {
+                <del class="diff-delete">{text}</del>
+            }),
+            ChangeTag::Insert => right.push(html! {
+                <ins class="diff-insert">{text}</ins>
+            }),
+        }
+    }
+    html! {
+        <ybc::Columns>
+            <ybc::Column>
+                <p class="has-text-weight-bold">{a_title}</p>
+                <pre class="diff-text">{ for left }</pre>
+            </ybc::Column>
+            <ybc::Column>
+                <p class="has-text-weight-bold">{b_title}</p>
+                <pre class="diff-text">{ for right }</pre>
+            </ybc::Column>
+        </ybc::Columns>
+    }
+}
@@ -0,0 +1,178 @@
+use crate::step::RegexFlags;
+use regex_syntax::hir::{self, Hir, HirKind};
+
+/// How many ranges of a class are listed before it's summarized instead.
+const MAX_LISTED_RANGES: usize = 4;
+
+/// Describes what the regex pattern matches, in English.
+///
+/// The description is built by rules from the parsed pattern, so it follows
+/// the pattern's structure rather than its intent. The flags are applied
+/// while parsing, so a case insensitive letter is described as a class.
+pub fn explain_regex(pattern: &str, flags: RegexFlags) -> String {
+    if pattern.is_empty() {
+        return "Matches nothing, so it's ignored.".into();
+    }
+    match flags.parser().parse(pattern) {
+        Ok(hir) => format!("Matches {}.", describe(&hir)),
+        Err(err) => format!("The pattern is not valid: {}", err),
+    }
+}
+
+fn describe(hir: &Hir) -> String {
+    match hir.kind() {
+        HirKind::Empty => "an empty text".into(),
+        HirKind::Literal(literal) => format!("a literal '{}'", literal_char(literal)),
+        HirKind::Class(class) => describe_class(class),
+        HirKind::Anchor(anchor) => match anchor {
+            hir::Anchor::StartLine => "the start of a line".into(),
+            hir::Anchor::EndLine => "the end of a line".into(),
+            hir::Anchor::StartText => "the start of the text".into(),
+            hir::Anchor::EndText => "the end of the text".into(),
+        },
+        HirKind::WordBoundary(boundary) => match boundary {
+            hir::WordBoundary::Unicode | hir::WordBoundary::Ascii => "a word boundary".into(),
+            hir::WordBoundary::UnicodeNegate | hir::WordBoundary::AsciiNegate => {
+                "a position that is not a word boundary".into()
+            }
+        },
+        HirKind::Repetition(repetition) => describe_repetition(repetition),
+        HirKind::Group(group) => {
+            let inner = describe(&group.hir);
+            match &group.kind {
+                hir::GroupKind::CaptureIndex(index) => {
+                    format!("{}, capturing it in group {}", inner, index)
+                }
+                hir::GroupKind::CaptureName { name, index } => {
+                    format!(
+                        "{}, capturing it in group {} named '{}'",
+                        inner, index, name
+                    )
+                }
+                hir::GroupKind::NonCapturing => inner,
+            }
+        }
+        HirKind::Concat(hirs) => describe_concat(hirs),
+        HirKind::Alternation(hirs) => {
+            let options: Vec<String> = hirs.iter().map(describe).collect();
+            format!("either {}", options.join(", or "))
+        }
+    }
+}
+
+fn literal_char(literal: &hir::Literal) -> char {
+    match literal {
+        hir::Literal::Unicode(c) => *c,
+        hir::Literal::Byte(b) => *b as char,
+    }
+}
+
+/// Describes the elements in order, joining consecutive literals into a
+/// single literal text.
+fn describe_concat(hirs: &[Hir]) -> String {
+    let mut parts = vec![];
+    let mut literal = String::new();
+    for hir in hirs {
+        match hir.kind() {
+            HirKind::Literal(l) => literal.push(literal_char(l)),
+            _ => {
+                if !literal.is_empty() {
+                    parts.push(format!("a literal '{}'", std::mem::take(&mut literal)));
+                }
+                parts.push(describe(hir));
+            }
+        }
+    }
+    if !literal.is_empty() {
+        parts.push(format!("a literal '{}'", literal));
+    }
+    parts.join(" followed by ")
+}
+
+fn describe_repetition(repetition: &hir::Repetition) -> String {
+    let inner = describe(&repetition.hir);
+    let described = match &repetition.kind {
+        hir::RepetitionKind::ZeroOrOne => format!("optionally {}", inner),
+        hir::RepetitionKind::ZeroOrMore => format!("zero or more of {}", inner),
+        hir::RepetitionKind::OneOrMore => format!("one or more of {}", inner),
+        hir::RepetitionKind::Range(range) => match range {
+            hir::RepetitionRange::Exactly(n) => format!("exactly {} of {}", n, inner),
+            hir::RepetitionRange::AtLeast(n) => format!("at least {} of {}", n, inner),
+            hir::RepetitionRange::Bounded(m, n) => {
+                format!("between {} and {} of {}", m, n, inner)
+            }
+        },
+    };
+    if repetition.greedy {
+        described
+    } else {
+        format!("{} (as few as possible)", described)
+    }
+}
+
+fn describe_class(class: &hir::Class) -> String {
+    let ranges: Vec<(char, char)> = match class {
+        hir::Class::Unicode(class) => class.iter().map(|r| (r.start(), r.end())).collect(),
+        hir::Class::Bytes(class) => class
+            .iter()
+            .map(|r| (r.start() as char, r.end() as char))
+            .collect(),
+    };
+    let contains = |c: char| ranges.iter().any(|&(start, end)| start <= c && c <= end);
+
+    // negated classes cover everything except a few gaps
+    let max = match class {
+        hir::Class::Unicode(_) => char::MAX,
+        hir::Class::Bytes(_) => '\u{FF}',
+    };
+    if ranges.first().map(|r| r.0) == Some('\0') && ranges.last().map(|r| r.1) == Some(max) {
+        let gaps: Vec<(char, char)> = ranges
+            .windows(2)
+            .filter_map(|w| {
+                let start = char::from_u32(w[0].1 as u32 + 1)?;
+                let end = char::from_u32(w[1].0 as u32 - 1)?;
+                Some((start, end))
+            })
+            .collect();
+        return match gaps.as_slice() {
+            [] => "any character".into(),
+            [('\n', '\n')] => "any character except a new line".into(),
+            gaps if gaps.len() <= MAX_LISTED_RANGES => {
+                format!("any character except {}", list_ranges(gaps))
+            }
+            _ => "a character outside of a set".into(),
+        };
+    }
+
+    if ranges.len() <= MAX_LISTED_RANGES {
+        return match ranges.as_slice() {
+            [(start, end)] if start == end => format!("a literal '{}'", start),
+            _ => format!("a character in {}", list_ranges(&ranges)),
+        };
+    }
+    if contains('0') && contains('9') && !contains('a') && !contains(' ') {
+        "a digit".into()
+    } else if contains('a') && contains('Z') && contains('0') && contains('_') && !contains(' ') {
+        "a word character".into()
+    } else if contains(' ') && contains('\t') && contains('\n') && !contains('a') {
+        "a whitespace".into()
+    } else if contains('a') && contains('Z') && !contains('0') {
+        "a letter".into()
+    } else {
+        format!("a character in one of {} ranges", ranges.len())
+    }
+}
+
+fn list_ranges(ranges: &[(char, char)]) -> String {
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|(start, end)| {
+            if start == end {
+                format!("{:?}", start)
+            } else {
+                format!("{:?}-{:?}", start, end)
+            }
+        })
+        .collect();
+    ranges.join(", ")
+}
@@ -0,0 +1,402 @@
+use crate::diff;
+use crate::step::Step;
+use crate::text_project::TextProject;
+use serde::Serialize;
+use similar::ChangeTag;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Makes the browser download `content` as a file.
+pub fn download_text_file(filename: &str, mime: &str, content: &str) {
+    if let Err(err) = try_download_text_file(filename, mime, content) {
+        log::error!("Failed to download {}: {:?}", filename, err);
+    }
+}
+
+fn try_download_text_file(filename: &str, mime: &str, content: &str) -> Result<(), JsValue> {
+    let parts = js_sys::Array::of1(&JsValue::from_str(content));
+    let mut props = web_sys::BlobPropertyBag::new();
+    props.type_(mime);
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(&parts, &props)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|w| w.document())
+        .ok_or_else(|| JsValue::from_str("no document available"))?;
+    let anchor: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
+
+/// Wraps the text in a Markdown code span that is also safe inside tables.
+fn markdown_code(text: &str) -> String {
+    if text.is_empty() {
+        return "".into();
+    }
+    let text = text.replace('|', "\\|").replace('\n', "\\n");
+    if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+/// Describes every step and regex as a Markdown document.
+pub fn steps_to_markdown(steps: &[Step]) -> String {
+    let mut md = String::from("# Replacer Steps\n");
+    for (i, step) in steps.iter().enumerate() {
+        let title = step.props.title.trim();
+        if title.is_empty() {
+            md += &format!("\n## {}. New Step\n\n", i + 1);
+        } else {
+            md += &format!("\n## {}. {}\n\n", i + 1, title);
+        }
+        let description = step.props.description.trim();
+        if !description.is_empty() {
+            md += &format!("{}\n\n", description);
+        }
+        if !step.props.enabled {
+            md += "_This step is disabled._\n\n";
+        }
+        if step.regexes.is_empty() {
+            md += "_This step has no regexes._\n";
+            continue;
+        }
+        md += "| Title | Pattern | Replacement | Flags |\n";
+        md += "| --- | --- | --- | --- |\n";
+        for r in step.regexes.iter() {
+            let pattern = match &r.r#match {
+                Ok(re) => re.as_str(),
+                Err(s) => s.as_str(),
+            };
+            md += &format!(
+                "| {} | {} | {} | {} |\n",
+                r.title.replace('|', "\\|"),
+                markdown_code(pattern),
+                markdown_code(&r.replace),
+                markdown_code(&r.flags.letters()),
+            );
+        }
+    }
+    md
+}
+
+/// The print-friendly stylesheet of the print view.
+const PRINT_STYLE: &str = "
+body { font-family: Georgia, serif; max-width: 50em; margin: 2em auto; color: #000; }
+h1, h2 { font-family: sans-serif; }
+pre { border: 1px solid #000; padding: 1em; white-space: pre-wrap; word-wrap: break-word; }
+table { border-collapse: collapse; width: 100%; font-size: 0.9em; }
+th, td { border: 1px solid #000; padding: 0.3em 0.5em; text-align: left; vertical-align: top; }
+code { white-space: pre-wrap; }
+del { background-color: #feecf0; color: #cc0f35; }
+ins { background-color: #effaf5; color: #257953; }
+@media print { body { margin: 0; max-width: none; } h2 { page-break-after: avoid; } }
+";
+
+/// Escapes the text so it's shown as-is inside HTML.
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Describes the project as a standalone HTML document meant for printing,
+/// with its texts, the changes between them and a table of the enabled
+/// steps' regexes.
+pub fn project_to_print_html(project: &TextProject, steps: &[Step]) -> String {
+    let title = project.props.title.trim();
+    let title = html_escape(if title.is_empty() {
+        "New Project"
+    } else {
+        title
+    });
+    let mut html = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>",
+        title, PRINT_STYLE
+    );
+    html += &format!("<h1>{}</h1>", title);
+    html += &format!(
+        "<h2>Original Text</h2><pre>{}</pre>",
+        html_escape(&project.input)
+    );
+    html += &format!("<h2>Result</h2><pre>{}</pre>", html_escape(&project.output));
+    html += "<h2>Changes</h2><pre>";
+    for (tag, text) in diff::word_changes(&project.input, &project.output) {
+        let text = html_escape(text);
+        match tag {
+            ChangeTag::Equal => html += &text,
+            ChangeTag::Delete => html += &format!("<del>{}</del>", text),
+            ChangeTag::Insert => html += &format!("<ins>{}</ins>", text),
+        }
+    }
+    html += "</pre>";
+    html += "<h2>Steps</h2><table><thead><tr><th>Step</th><th>Regex</th><th>Pattern</th><th>Replacement</th><th>Flags</th></tr></thead><tbody>";
+    for (i, step) in steps.iter().enumerate().filter(|(_, s)| s.props.enabled) {
+        let step_title = step.props.title.trim();
+        let step_title = if step_title.is_empty() {
+            "New Step"
+        } else {
+            step_title
+        };
+        for r in step.regexes.iter() {
+            let pattern = match &r.r#match {
+                Ok(re) => re.as_str(),
+                Err(s) => s.as_str(),
+            };
+            html += &format!(
+                "<tr><td>{}. {}</td><td>{}</td><td><code>{}</code></td><td><code>{}</code></td><td>{}</td></tr>",
+                i + 1,
+                html_escape(step_title),
+                html_escape(&r.title),
+                html_escape(pattern),
+                html_escape(&r.replace),
+                r.flags.letters(),
+            );
+        }
+    }
+    html += "</tbody></table></body></html>";
+    html
+}
+
+/// Opens the HTML document in a new browser tab.
+pub fn open_html_in_new_tab(html: &str) {
+    if let Err(err) = try_open_html_in_new_tab(html) {
+        log::error!("Failed to open the new tab: {:?}", err);
+    }
+}
+
+fn try_open_html_in_new_tab(html: &str) -> Result<(), JsValue> {
+    let window = web_sys::window()
+        .ok_or_else(|| JsValue::from_str("no window available"))?
+        .open_with_url_and_target("", "_blank")?
+        .ok_or_else(|| JsValue::from_str("the new tab was blocked"))?;
+    let document: web_sys::HtmlDocument = window
+        .document()
+        .ok_or_else(|| JsValue::from_str("no document available"))?
+        .dyn_into()?;
+    document.write(&js_sys::Array::of1(&JsValue::from_str(html)))?;
+    document.close()
+}
+
+/// A regex in the format of common regex replacement APIs, such as
+/// JavaScript's `String.replace`.
+#[derive(Serialize)]
+struct ToolRegex {
+    pattern: String,
+    replacement: String,
+    flags: String,
+}
+
+/// Removes the whitespace and `#` comments of a verbose pattern, so it fits
+/// in a single line.
+///
+/// As in the regex parser, they are ignored everywhere but in escapes, even
+/// inside classes.
+pub fn strip_verbose(pattern: &str) -> String {
+    let mut stripped = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                stripped.push(c);
+                stripped.extend(chars.next());
+            }
+            c if c.is_whitespace() => {}
+            '#' => while chars.next_if(|&c| c != '\n').is_some() {},
+            c => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// Rewrites the pattern in JavaScript syntax, where named groups are written
+/// as `(?<name>...)` and there is no verbose flag, so the whitespace and
+/// comments of a verbose pattern are removed.
+pub fn pattern_to_js(pattern: &str, verbose: bool) -> String {
+    let stripped;
+    let pattern = if verbose {
+        stripped = strip_verbose(pattern);
+        &stripped
+    } else {
+        pattern
+    };
+    let mut js = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    // classes can be nested, as in `[a-z&&[^aeiou]]`
+    let mut class_depth = 0;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                js.push(c);
+                js.extend(chars.next());
+            }
+            '[' => {
+                class_depth += 1;
+                js.push(c);
+                if chars.peek() == Some(&'^') {
+                    js.extend(chars.next());
+                }
+                // a leading `]` is a literal
+                if chars.peek() == Some(&']') {
+                    js.extend(chars.next());
+                }
+            }
+            ']' if class_depth > 0 => {
+                class_depth -= 1;
+                js.push(c);
+            }
+            '(' if class_depth == 0 && chars.clone().take(3).eq("?P<".chars()) => {
+                js.push_str("(?<");
+                chars.nth(2);
+            }
+            c => js.push(c),
+        }
+    }
+    js
+}
+
+/// Rewrites the replacement in JavaScript syntax, where named groups are
+/// referenced as `$<name>` and a literal `$` is written as `$$`.
+pub fn replacement_to_js(replacement: &str) -> String {
+    let mut js = String::with_capacity(replacement.len());
+    let mut rest = replacement;
+    while let Some(pos) = rest.find('$') {
+        js.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        let (name, after) = match rest.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], &braced[end + 1..]),
+                None => ("", rest),
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            }
+        };
+        if name.is_empty() {
+            // either `$$` or a `$` that doesn't reference a group, which are
+            // both a literal `$`
+            js.push_str("$$");
+            rest = rest.strip_prefix('$').unwrap_or(rest);
+        } else if name.bytes().all(|b| b.is_ascii_digit()) {
+            js.push('$');
+            js.push_str(name);
+            rest = after;
+        } else {
+            js.push_str(&format!("$<{}>", name));
+            rest = after;
+        }
+    }
+    js.push_str(rest);
+    js
+}
+
+/// Writes the step's regexes as a JSON array of
+/// `{"pattern", "replacement", "flags"}` objects, in JavaScript syntax, for
+/// use in other tools.
+///
+/// Every regex gets the global `g` flag, as each match is replaced.
+pub fn step_to_tool_json(step: &Step) -> String {
+    let regexes: Vec<ToolRegex> = step
+        .regexes
+        .iter()
+        .map(|r| {
+            let mut flags = String::from("g");
+            if r.flags.case_insensitive {
+                flags.push('i');
+            }
+            if r.flags.multiline {
+                flags.push('m');
+            }
+            if r.flags.dot_all {
+                flags.push('s');
+            }
+            let pattern = match &r.r#match {
+                Ok(re) => re.as_str(),
+                Err(s) => s.as_str(),
+            };
+            ToolRegex {
+                pattern: pattern_to_js(pattern, r.flags.verbose),
+                replacement: replacement_to_js(&r.replace),
+                flags,
+            }
+        })
+        .collect();
+    serde_json::to_string_pretty(&regexes).unwrap_or_default()
+}
+
+/// Writes each regex of the step as a `# <title>` line followed by its match,
+/// the format read by [`crate::step::regexes_from_text`].
+///
+/// Verbose matches are stripped to a single line and prefixed by `(?x)`.
+pub fn step_to_text(step: &Step) -> String {
+    let mut text = String::new();
+    for r in step.regexes.iter() {
+        let pattern = match &r.r#match {
+            Ok(re) => re.as_str(),
+            Err(s) => s.as_str(),
+        };
+        if r.flags.verbose {
+            text += &format!("# {}\n(?x){}\n\n", r.title, strip_verbose(pattern));
+        } else {
+            text += &format!("# {}\n{}\n\n", r.title, pattern);
+        }
+    }
+    text
+}
+
+/// Turns a title into something that is safe to use in a file name.
+pub fn file_name_safe(title: &str) -> String {
+    let name = title
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        "untitled".into()
+    } else {
+        name.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_to_js_renames_named_groups() {
+        assert_eq!(pattern_to_js(r"(?P<year>\d{4})", false), r"(?<year>\d{4})");
+        // not inside classes
+        assert_eq!(pattern_to_js(r"[(?P<]", false), r"[(?P<]");
+    }
+
+    #[test]
+    fn pattern_to_js_keeps_lookbehinds() {
+        assert_eq!(pattern_to_js(r"(?<=a)(?<!b)c", false), r"(?<=a)(?<!b)c");
+    }
+
+    #[test]
+    fn pattern_to_js_strips_verbose_classes() {
+        assert_eq!(pattern_to_js("[a b] # letters\n c", true), "[ab]c");
+        assert_eq!(pattern_to_js("[a # not b\n c]", true), "[ac]");
+        assert_eq!(pattern_to_js(r"a\ b\#", true), r"a\ b\#");
+        assert_eq!(pattern_to_js("[a b]", false), "[a b]");
+    }
+
+    #[test]
+    fn replacement_to_js_references_groups() {
+        assert_eq!(replacement_to_js("$$"), "$$");
+        assert_eq!(replacement_to_js("${name}"), "$<name>");
+        assert_eq!(replacement_to_js("$name."), "$<name>.");
+        assert_eq!(replacement_to_js("${1}"), "$1");
+        assert_eq!(replacement_to_js("$ and $"), "$$ and $$");
+    }
+}
@@ -0,0 +1,139 @@
+use yew::prelude::*;
+
+/// The kind of a regex pattern token.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Literal,
+    Escape,
+    Class,
+    Group,
+    Alternation,
+    Quantifier,
+    Anchor,
+}
+
+impl TokenKind {
+    fn class(self) -> &'static str {
+        match self {
+            TokenKind::Literal => "re-literal",
+            TokenKind::Escape => "re-escape",
+            TokenKind::Class => "re-class",
+            TokenKind::Group => "re-group",
+            TokenKind::Alternation => "re-alternation",
+            TokenKind::Quantifier => "re-quantifier",
+            TokenKind::Anchor => "re-anchor",
+        }
+    }
+}
+
+/// Splits a regex pattern into tokens, as `(kind, text)`.
+///
+/// This is a lenient tokenizer for display only, so invalid patterns are
+/// still tokenized as well as possible.
+fn tokenize(pattern: &str) -> Vec<(TokenKind, String)> {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut tokens: Vec<(TokenKind, String)> = vec![];
+    let mut i = 0;
+
+    // takes an escape sequence starting at `i`, returning its end
+    let escape_end = |i: usize| -> usize {
+        let mut end = (i + 2).min(chars.len());
+        if end < chars.len()
+            && matches!(chars[i + 1], 'p' | 'P' | 'x' | 'u' | 'U')
+            && chars[end] == '{'
+        {
+            while end < chars.len() && chars[end] != '}' {
+                end += 1;
+            }
+            end = (end + 1).min(chars.len());
+        } else if end < chars.len() && matches!(chars[i + 1], 'p' | 'P') {
+            // single letter unicode class, such as `\pL`
+            end += 1;
+        }
+        end
+    };
+
+    while i < chars.len() {
+        let (kind, end) = match chars[i] {
+            '\\' => (TokenKind::Escape, escape_end(i)),
+            '[' => {
+                let mut end = i + 1;
+                let mut depth = 1;
+                // a leading `]` (or `^]`) is a literal
+                if end < chars.len() && chars[end] == '^' {
+                    end += 1;
+                }
+                if end < chars.len() && chars[end] == ']' {
+                    end += 1;
+                }
+                while end < chars.len() && depth > 0 {
+                    match chars[end] {
+                        '\\' => end = escape_end(end) - 1,
+                        '[' => depth += 1,
+                        ']' => depth -= 1,
+                        _ => {}
+                    }
+                    end += 1;
+                }
+                (TokenKind::Class, end)
+            }
+            '(' => {
+                let mut end = i + 1;
+                if end < chars.len() && chars[end] == '?' {
+                    // group flags or names, such as `(?:`, `(?i)` or `(?P<name>`
+                    while end < chars.len() && !matches!(chars[end], ':' | ')' | '>') {
+                        end += 1;
+                    }
+                    end = (end + 1).min(chars.len());
+                }
+                (TokenKind::Group, end)
+            }
+            ')' => (TokenKind::Group, i + 1),
+            '|' => (TokenKind::Alternation, i + 1),
+            '*' | '+' | '?' => {
+                let mut end = i + 1;
+                // lazy quantifier
+                if end < chars.len() && chars[end] == '?' {
+                    end += 1;
+                }
+                (TokenKind::Quantifier, end)
+            }
+            '{' => {
+                let mut end = i + 1;
+                while end < chars.len() && (chars[end].is_ascii_digit() || chars[end] == ',') {
+                    end += 1;
+                }
+                if end < chars.len() && chars[end] == '}' && end > i + 1 {
+                    end += 1;
+                    if end < chars.len() && chars[end] == '?' {
+                        end += 1;
+                    }
+                    (TokenKind::Quantifier, end)
+                } else {
+                    (TokenKind::Literal, i + 1)
+                }
+            }
+            '^' | '$' | '.' => (TokenKind::Anchor, i + 1),
+            _ => (TokenKind::Literal, i + 1),
+        };
+        let text: String = chars[i..end].iter().collect();
+        match tokens.last_mut() {
+            // merges consecutive literals
+            Some((TokenKind::Literal, last)) if kind == TokenKind::Literal => last.push_str(&text),
+            _ => tokens.push((kind, text)),
+        }
+        i = end;
+    }
+    tokens
+}
+
+/// Renders the regex pattern with each kind of token in a distinct color.
+pub fn regex_highlight(pattern: &str) -> Html {
+    html! {
+        <>
+        { for tokenize(pattern).into_iter().map(|(kind, text)| html! {
+            <span class={kind.class()}>{text}</span>
+        })}
+        </>
+    }
+}
@@ -1,22 +1,45 @@
 #![feature(stmt_expr_attributes)]
 
+pub mod cheatsheet;
+pub mod complexity;
+pub mod diagram;
+pub mod diff;
+pub mod explain;
+pub mod export;
+pub mod highlight;
+pub mod notification;
+pub mod patterns;
+pub mod settings;
+pub mod stats;
 pub mod step;
+pub mod templates;
 pub mod text_project;
+pub mod variables;
 
-use indexmap::IndexSet;
+use futures::channel::mpsc::Sender;
+use indexmap::{IndexMap, IndexSet};
+use notification::Notification;
 use regex::Regex;
+use settings::{FontSize, GlobalSettings, SettingsDraft, Theme};
+use stats::{RunInProgress, RunRecord};
+use std::collections::VecDeque;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use step::{RegexInfo, Step};
+use step::{
+    AbConfig, RegexFlags, RegexInfo, RegexTestCase, Step, StepProps, TestCaseField, VirtualSort,
+};
 use text_project::CancelMotive;
-use text_project::{OutputStatus, TextProject};
+use text_project::{InputFetchStatus, OutputStatus, ProjectEvent, TextProject, WebhookStatus};
 use yew::prelude::*;
 
 pub type StepIndex = usize;
 pub type RegexIndex = usize;
 pub type ProjectIndex = usize;
-pub type Confirmed = bool;
+pub type TestCaseIndex = usize;
+
+/// Approximate size, in bytes, of each chunk of a streaming replacement.
+pub const DEFAULT_STREAM_CHUNK_SIZE: usize = 16 * 1024;
 
 pub enum MoveDirection {
     Up,
@@ -27,27 +50,261 @@ pub enum Msg {
     // Step
     AddStep,
     SelectStep(StepIndex),
+    /// Opens the step for editing, if it isn't already.
+    OpenStepEditor(StepIndex),
+    /// Opens the step for editing and scrolls the page to the regex.
+    JumpToRegex(StepIndex, RegexIndex),
+    /// Shows only the title of the other regexes of the step, or shows every
+    /// regex again if the regex is already the active one.
+    SetActiveRegex(StepIndex, RegexIndex),
+    CloseAllStepEditors,
+    OpenAllStepEditors,
     SetStepEnabled(StepIndex, bool),
+    SetStepVirtualSort(StepIndex, VirtualSort),
+    SetStepRunOnlyIfPrevChanged(StepIndex, bool),
+    SetAllStepsEnabled(bool),
+    SetStepStopOnFirstMatch(StepIndex, bool),
+    SetStepInConfig(StepIndex, AbConfig, bool),
     UpdateStepTitle(StepIndex, String),
+    UpdateStepDescription(StepIndex, String),
     AddRegex(StepIndex),
     UpdateRegexTitle(StepIndex, RegexIndex, String),
     UpdateRegexSearch(StepIndex, RegexIndex, String),
+    /// Searches the titles, matches and replacements of every regex.
+    UpdateGlobalRegexSearch(String),
     UpdateRegexReplacement(StepIndex, RegexIndex, String),
-    DeleteRegex(StepIndex, RegexIndex, Confirmed),
+    /// Replaces the regex's match and title by the ones of a
+    /// [`patterns::COMMON_PATTERNS`] entry.
+    InsertCommonPattern(StepIndex, RegexIndex, &'static str),
+    UpdateNewGroupName(StepIndex, RegexIndex, String),
+    /// Appends a named group to the match, referenced by the replacement.
+    AddNamedGroup(StepIndex, RegexIndex, String),
+    /// Renames a named group in both the match and the replacement.
+    RenameRegexGroup(StepIndex, RegexIndex, String, String),
+    SetRegexSkipIfPrevNoMatch(StepIndex, RegexIndex, bool),
+    SetRegexApplyOncePerLine(StepIndex, RegexIndex, bool),
+    SetRegexEnabled(StepIndex, RegexIndex, bool),
+    SetAllRegexesEnabled(StepIndex, bool),
+    UpdateRegexFlags(StepIndex, RegexIndex, RegexFlags),
+    DeleteRegex(StepIndex, RegexIndex),
     MoveRegex(StepIndex, RegexIndex, MoveDirection),
+    /// Moves the regex to another position of the same step, shifting the
+    /// regexes in between.
+    MoveRegexTo(StepIndex, RegexIndex, RegexIndex),
+    MoveRegexToStep(StepIndex, RegexIndex, StepIndex),
+    CopyRegexToStep(StepIndex, RegexIndex, StepIndex),
+    DuplicateRegex(StepIndex, RegexIndex),
+    MergeSteps(StepIndex),
+    /// Moves the step to another position, shifting the steps in between.
+    MoveStepTo(StepIndex, StepIndex),
+    ImportRegexesFromText(StepIndex, String),
+    PasteRegexesFromClipboard(StepIndex),
+    ClipboardRegexesRead(StepIndex, Result<String, String>),
+    /// Pastes the regexes of a JSON object, or array of objects, from the
+    /// clipboard.
+    PasteRegexFromJsonClipboard(StepIndex),
+    ClipboardJsonRegexesRead(StepIndex, Result<String, String>),
+    LoadTemplate(usize),
+    ExportAsMarkdown,
+    ExportStepAsText(StepIndex),
+    /// Downloads the step's regexes as JSON, for use in other regex tools.
+    ExportStepAsToolJson(StepIndex),
+    SplitStep(StepIndex, RegexIndex),
+    AddTestCase(StepIndex, RegexIndex),
+    UpdateTestCase(StepIndex, RegexIndex, TestCaseIndex, TestCaseField, String),
+    RunTestCases(StepIndex, RegexIndex),
+    /// Runs the test cases of every enabled regex of the step.
+    TestAllRegexesInStep(StepIndex),
+    ShowRegexDiagram(StepIndex, RegexIndex),
+    BenchmarkRegex(StepIndex, RegexIndex),
+    AnalyzeRegexComplexity(StepIndex, RegexIndex),
+    HighlightRegexMatches(Option<(StepIndex, RegexIndex)>),
+    ToggleCheatsheet,
+    /// Whether the steps dropdown of the navbar is shown, as it opens on
+    /// hover and focus.
+    SetStepsDropdownExpanded(bool),
+    DismissWelcome,
+    InsertIntoRegex(&'static str),
+    CloseRegexDiagram,
+    ResetToDefaults,
+    ShowStats(bool),
+    OpenSettings,
+    CloseSettings,
+    UpdateSettings(GlobalSettings),
+    UpdateSettingsDraft(SettingsDraft),
+    /// Validates the typed numeric settings, and applies them if they are
+    /// valid.
+    ApplySettingsDraft,
+    /// Switches between the light and dark themes.
+    ToggleDarkMode,
+    ShowVariables(bool),
+    SetVariable(String, String),
+    DeleteVariable(String),
+    UpdateNewVariableName(Option<ProjectIndex>, String),
+    UpdateNewVariableValue(Option<ProjectIndex>, String),
+    AddVariable,
+    ShowProjectVariables(bool),
+    SetProjectVariable(ProjectIndex, String, String),
+    DeleteProjectVariable(ProjectIndex, String),
+    AddProjectVariable(ProjectIndex),
+    /// Asks the user to confirm the action before it's dispatched.
+    RequestConfirmation(PendingAction),
+    ConfirmPendingAction,
+    CancelPendingAction,
+    AddNotification(Notification),
+    DismissNotification(usize),
+    DismissExpiredNotifications,
 
     // Text Project
     AddTextProject,
+    UpdateSplitDelimiter(String),
+    SplitClipboardIntoProjects(String),
+    ClipboardProjectsRead(String, Result<String, String>),
+    CloneProject(ProjectIndex),
     SelectTextProject(ProjectIndex),
     UpdateTextProjectTitle(ProjectIndex, String),
+    UpdateWebhookUrl(ProjectIndex, String),
+    /// Keeps the project's current output in its archive.
+    ArchiveOutput(ProjectIndex),
+    UpdateNewTag(String),
+    AddProjectTag(ProjectIndex, String),
+    RemoveProjectTag(ProjectIndex, String),
+    /// Shows only the tabs of the projects that have the tag, or every tab
+    /// if `None`.
+    FilterTabsByTag(Option<String>),
+    SendOutputToWebhook(ProjectIndex),
+    WebhookResponded(ProjectIndex, Result<u16, String>),
     StartReplacingText(Option<ProjectIndex>),
+    StartStreamingReplacement(ProjectIndex, usize),
+    StartABComparison(ProjectIndex),
+    /// The A/B comparison finished, with the text of each configuration and
+    /// why it was cancelled, if it was.
+    FinishABComparison(
+        ProjectIndex,
+        (String, Option<CancelMotive>),
+        (String, Option<CancelMotive>),
+    ),
+    /// A configuration of the A/B comparison completed some of its steps,
+    /// out of the total.
+    UpdateABProgress(AbConfig, usize, usize),
+    CloseABComparison(ProjectIndex),
+    AppendStreamedChunk(ProjectIndex, String, usize, usize),
+    /// The replacement of the project completed some of the steps, and the
+    /// running step's regex may have just fired.
+    UpdateProgress(ProjectIndex, usize, usize, Option<RegexIndex>),
+    /// Estimates how many seconds are left for the replacement in progress.
+    UpdateEta(Eta),
+    SetStepPauseBetweenRegexes(StepIndex, Option<u64>),
+    SetStepPriority(StepIndex, usize),
+    AppendLog(LogEntry),
+    ToggleExecutionLog,
     CancelReplacingText(),
-    FinishReplacingText(ProjectIndex, String),
+    PauseReplacement,
+    ResumeReplacement,
+    /// Shows the text replaced so far by the paused replacement.
+    ShowPausedOutput(ProjectIndex, String),
+    FinishReplacingText(ProjectIndex, ReplacementResult),
     CancelledReplacingText(ProjectIndex, CancelMotive, String),
 
     // Input/Output
     InputUpdated(ProjectIndex, String),
     OutputUpdated(ProjectIndex, String),
+    ToggleOutputSearch,
+    UpdateOutputSearch(String),
+    SetOutputSearchCaseInsensitive(bool),
+    MoveOutputSearch(MoveDirection),
+    ShowInputToolbar(bool),
+    ToggleInputCollapsed,
+    SyncScroll(TextAreaId, f64),
+    ToggleInputWrap(ProjectIndex),
+    ToggleOutputWrap(ProjectIndex),
+    ToggleRtlMode(ProjectIndex),
+    /// Opens a print-friendly document of the project in a new tab.
+    GeneratePrintView(ProjectIndex),
+    /// Downloads the print view of the project as an HTML file.
+    ExportProjectAsHtml(ProjectIndex),
+    UpdateInputUrl(ProjectIndex, String),
+    LoadInputFromUrl(ProjectIndex, String),
+    InputFetched(ProjectIndex, Result<String, String>),
+    FindInInput(String),
+    UpdateInputReplaceText(String),
+    ReplaceInInput(String, String),
+    ReplaceAllInInput(String, String),
+}
+
+/// A destructive action that waits for the user's confirmation.
+#[derive(Clone, Copy)]
+pub enum PendingAction {
+    DeleteRegex(StepIndex, RegexIndex),
+    ResetToDefaults,
+    /// Loads the template over the current steps.
+    LoadTemplate(usize),
+}
+
+impl PendingAction {
+    /// Explains what is lost if the action is confirmed.
+    pub fn description(&self) -> String {
+        match self {
+            PendingAction::DeleteRegex(step_index, regex_index) => format!(
+                "Regex {} of step {} will be removed.",
+                regex_index + 1,
+                step_index + 1
+            ),
+            PendingAction::ResetToDefaults => "Every step and text project will be removed and replaced by empty ones. This cannot be undone.".into(),
+            PendingAction::LoadTemplate(template_index) => format!(
+                r#"The current steps will be replaced by the "{}" template."#,
+                templates::TEMPLATES[*template_index].title
+            ),
+        }
+    }
+
+    pub fn confirm_label(&self) -> &'static str {
+        match self {
+            PendingAction::DeleteRegex(..) => "Delete Regex",
+            PendingAction::ResetToDefaults => "Reset to Defaults",
+            PendingAction::LoadTemplate(_) => "Load Template",
+        }
+    }
+
+    pub fn into_msg(self) -> Msg {
+        match self {
+            PendingAction::DeleteRegex(step_index, regex_index) => {
+                Msg::DeleteRegex(step_index, regex_index)
+            }
+            PendingAction::ResetToDefaults => Msg::ResetToDefaults,
+            PendingAction::LoadTemplate(template_index) => Msg::LoadTemplate(template_index),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct OutputSearch {
+    pub query: String,
+    pub case_insensitive: bool,
+    /// Index of the highlighted match.
+    pub current: usize,
+}
+
+impl OutputSearch {
+    /// Finds the byte ranges of every match of the query in the text.
+    ///
+    /// A query too long to be compiled has no matches.
+    pub fn find_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+        if self.query.is_empty() {
+            return vec![];
+        }
+        let re = match regex::RegexBuilder::new(&regex::escape(&self.query))
+            .case_insensitive(self.case_insensitive)
+            .build()
+        {
+            Ok(re) => re,
+            Err(err) => {
+                log::warn!("The output search can't be compiled: {}", err);
+                return vec![];
+            }
+        };
+        re.find_iter(text).map(|m| (m.start(), m.end())).collect()
+    }
 }
 
 pub struct Model {
@@ -56,6 +313,36 @@ pub struct Model {
     pub active_text_project: Option<usize>,
     pub replacement_in_progress: bool,
     pub replacement_cancel_signal: Arc<AtomicBool>,
+    /// Holds the replacement in progress while it's set, so its
+    /// intermediate text can be inspected.
+    pub pause_signal: Arc<AtomicBool>,
+    /// Estimated seconds left for the replacement in progress.
+    pub replacement_eta: Option<Eta>,
+    /// Completed steps, out of the total, of configurations A and B while
+    /// an A/B comparison is in progress.
+    pub ab_progress: Option<[(usize, usize); 2]>,
+    /// The project whose auto-run restarts once the replacement in progress
+    /// is cancelled, because its input was edited meanwhile.
+    pub auto_run_restart: Option<ProjectIndex>,
+    /// Whether an input was edited while the replacement was in progress,
+    /// so its output is outdated once it finishes.
+    pub input_edited_during_run: bool,
+    pub execution_log: Vec<LogEntry>,
+    pub execution_log_open: bool,
+
+    // output search
+    pub output_search: Option<OutputSearch>,
+    pub scroll_to_output_search: bool,
+    /// The regex that the page is scrolled to after the next render.
+    pub scroll_to_regex: Option<(StepIndex, RegexIndex)>,
+
+    // whether the input textarea is replaced by a summary
+    pub input_collapsed: bool,
+
+    // input find-and-replace
+    pub input_toolbar_open: bool,
+    pub input_find: String,
+    pub input_replace: String,
 
     // steps
     pub steps: Vec<Step>,
@@ -63,23 +350,407 @@ pub struct Model {
 
     // regexes
     pub active_regex_index: Option<usize>,
+    /// Regex whose railroad diagram is being shown.
+    pub regex_diagram: Option<(StepIndex, RegexIndex)>,
+    /// Regex being edited, whose matches are highlighted on the input.
+    pub highlighted_regex: Option<(StepIndex, RegexIndex)>,
+    /// Byte ranges of the input matched by the highlighted regex.
+    pub highlighted_ranges: Vec<(usize, usize)>,
+    /// The regex whose match field was focused the latest, which the
+    /// cheatsheet inserts into.
+    pub last_focused_regex: Option<(StepIndex, RegexIndex)>,
+    /// The only regex of its step that is fully shown, while the others are
+    /// collapsed to their title.
+    pub active_regex: Option<(StepIndex, RegexIndex)>,
+    pub cheatsheet_open: bool,
+    pub steps_dropdown_expanded: bool,
+    /// What separates the texts that are split from the clipboard into
+    /// projects, with `\n` and `\t` escapes.
+    pub split_delimiter: String,
+    /// The tag being written, before it's added to the active project.
+    pub new_tag: String,
+    /// The tag that the project tabs are filtered by.
+    pub tag_filter: Option<String>,
+    /// The name of the named group being added or renamed to, and the regex
+    /// that it's written on.
+    pub new_group_name: Option<((StepIndex, RegexIndex), String)>,
+    /// What the regexes of every step are searched for.
+    pub regex_search_query: String,
+    /// The latest replacement status, announced by screen readers.
+    pub screen_reader_message: String,
+    /// Keeps the highlight overlay and the line numbers scrolled along with
+    /// the input textarea.
+    pub input_scroll_listener: Option<(web_sys::Element, gloo_events::EventListener)>,
+    /// Keeps the line numbers scrolled along with the output textarea.
+    pub output_scroll_listener: Option<(web_sys::Element, gloo_events::EventListener)>,
+    /// How far, in pixels, the input textarea is scrolled down.
+    pub input_scroll_top: f64,
+    /// How far, in pixels, the output textarea is scrolled down.
+    pub output_scroll_top: f64,
+
+    /// The action whose confirmation is being shown.
+    pub pending_confirmation: Option<PendingAction>,
+    /// Messages for the user, from the oldest to the most recent.
+    pub notifications: VecDeque<Notification>,
+
+    // run statistics
+    pub current_run: Option<RunInProgress>,
+    pub run_history: Vec<RunRecord>,
+    pub stats_open: bool,
+
+    // settings
+    pub settings: GlobalSettings,
+    pub settings_open: bool,
+    pub settings_draft: SettingsDraft,
+
+    // replacement variables
+    /// Values of the `$NAME` references in the replacements.
+    pub variables: IndexMap<String, String>,
+    pub variables_open: bool,
+    /// Whether the local variables editor of the active project is open.
+    pub project_variables_open: bool,
+    pub new_variable: NewVariable,
+}
+
+/// A variable being written, before it's added.
+#[derive(Default)]
+pub struct NewVariable {
+    /// The project whose local variables it's added to, or `None` for the
+    /// global variables.
+    pub project_index: Option<ProjectIndex>,
+    pub name: String,
+    pub value: String,
+}
+
+impl NewVariable {
+    /// The name and value written on the editor of these variables, which
+    /// are empty if they are being written on another editor.
+    pub fn written_on(&self, project_index: Option<ProjectIndex>) -> (&str, &str) {
+        if self.project_index == project_index {
+            (&self.name, &self.value)
+        } else {
+            ("", "")
+        }
+    }
+
+    /// Starts writing on the editor of these variables, dropping what was
+    /// written on another editor.
+    pub fn write_on(&mut self, project_index: Option<ProjectIndex>) -> &mut Self {
+        if self.project_index != project_index {
+            *self = Self {
+                project_index,
+                ..Default::default()
+            };
+        }
+        self
+    }
+}
+
+/// A compiled regex of a step, as used by a replacement.
+#[derive(Clone)]
+pub struct StepRegex {
+    /// The position of the regex in its step, counting the empty and
+    /// disabled regexes that were left out of the replacement.
+    pub index: RegexIndex,
+    /// The regex title, which some orderings sort by.
+    pub title: String,
+    pub re: Regex,
+    pub replacement: String,
+    /// Whether this regex is only tried if the previous one made the latest
+    /// substitution.
+    pub skip_if_prev_no_match: bool,
+    /// Whether this regex is applied line by line, skipping the lines that
+    /// were already substituted during the step.
+    pub apply_once_per_line: bool,
+}
+
+/// Limits that end a replacement, or one of its steps, early.
+#[derive(Clone, Copy)]
+pub struct ReplacementLimits {
+    /// The time, in milliseconds since the epoch, after which the
+    /// replacement is cancelled.
+    pub deadline: Option<f64>,
+    /// How many times longer than the original text the content can grow
+    /// before the replacement is cancelled.
+    pub growth_factor: f64,
+    /// How many substitutions a step makes before moving on to the next step.
+    pub max_iterations: Option<usize>,
+}
+
+pub struct ReplacementResult {
+    pub content: String,
+    /// Accumulated time, in milliseconds, that each regex of each step spent
+    /// matching and replacing.
+    pub regex_durations: Vec<Vec<f64>>,
+    /// How many matches each regex of each step replaced.
+    pub regex_matches: Vec<Vec<usize>>,
+}
+
+impl ReplacementResult {
+    /// Appends the content of another result, summing up their durations and
+    /// match counts.
+    pub fn append(&mut self, other: ReplacementResult) {
+        self.content.push_str(&other.content);
+        if self.regex_durations.is_empty() {
+            self.regex_durations = other.regex_durations;
+            self.regex_matches = other.regex_matches;
+            return;
+        }
+        for (durations, other_durations) in self
+            .regex_durations
+            .iter_mut()
+            .zip(other.regex_durations.into_iter())
+        {
+            for (d, other_d) in durations.iter_mut().zip(other_durations.into_iter()) {
+                *d += other_d;
+            }
+        }
+        for (matches, other_matches) in self
+            .regex_matches
+            .iter_mut()
+            .zip(other.regex_matches.into_iter())
+        {
+            for (m, other_m) in matches.iter_mut().zip(other_matches.into_iter()) {
+                *m += other_m;
+            }
+        }
+    }
+}
+
+/// An estimate of how many seconds are left for a replacement.
+#[derive(Clone, Copy)]
+pub enum Eta {
+    /// Assumes that every remaining step runs up to its maximum iterations,
+    /// so the replacement shouldn't take longer.
+    AtMost(f64),
+    /// Assumes that the remaining steps take as long as the completed ones
+    /// did, on average.
+    About(f64),
+}
+
+/// What a replacement reports while it runs.
+#[derive(Clone)]
+pub struct ReplacementCallbacks {
+    /// Called with how many steps are completed, out of the total, and the
+    /// regex that just fired if the running step pauses after its
+    /// substitutions.
+    pub progress: Callback<(usize, usize, Option<RegexIndex>)>,
+    /// Called with the estimated seconds left.
+    pub eta: Callback<Eta>,
+    /// Called with the text replaced so far, once the replacement is paused.
+    pub paused: Callback<String>,
+}
+
+impl ReplacementCallbacks {
+    /// Callbacks that ignore what the replacement reports.
+    pub fn noop() -> Self {
+        Self {
+            progress: Callback::noop(),
+            eta: Callback::noop(),
+            paused: Callback::noop(),
+        }
+    }
+}
+
+/// A substitution made during a replacement, or a skipped step.
+pub struct LogEntry {
+    pub step_index: StepIndex,
+    pub regex_index: RegexIndex,
+    /// How many times the step regexes had already restarted.
+    pub iteration: usize,
+    /// The first matched substring.
+    pub matched: String,
+    /// What the first matched substring was replaced with.
+    pub replacement: String,
+    /// Whether the whole step was skipped instead, in which case there was
+    /// no substitution.
+    pub skipped: bool,
+}
+
+/// A substitution made on the content, as its byte range before and after
+/// it was made.
+type Edit = (std::ops::Range<usize>, std::ops::Range<usize>);
+
+/// Moves a byte position of the content to where it is after the `edits`,
+/// which are sorted by position.
+///
+/// A position inside of an edit moves to its start or, if it's the end of a
+/// range, to its end.
+fn shift_position(pos: usize, edits: &[Edit], is_end: bool) -> usize {
+    let mut shifted = pos;
+    for (old, new) in edits {
+        if old.end < pos || (old.end == pos && (is_end || old.start < old.end)) {
+            shifted = pos - old.end + new.end;
+        } else {
+            if old.start < pos {
+                shifted = if is_end { new.end } else { new.start };
+            }
+            break;
+        }
+    }
+    shifted
+}
+
+/// Moves the byte ranges of the processed lines so they keep covering the
+/// same text after the `edits`.
+fn shift_processed_lines(processed_lines: &mut [std::ops::Range<usize>], edits: &[Edit]) {
+    if edits.is_empty() {
+        return;
+    }
+    for range in processed_lines.iter_mut() {
+        *range = shift_position(range.start, edits, false)..shift_position(range.end, edits, true);
+    }
+}
+
+/// Applies the replacement to each line that wasn't yet processed, and marks
+/// the lines that it changes as processed.
+///
+/// The processed lines are tracked by their byte ranges, so they stay correct
+/// when a replacement adds or removes lines.
+///
+/// Returns the first match and its replacement, if any line matched.
+fn replace_unprocessed_lines(
+    re: &Regex,
+    replacement: &str,
+    content: &mut String,
+    processed_lines: &mut Vec<std::ops::Range<usize>>,
+    matches: &mut usize,
+) -> Option<(String, String)> {
+    let mut first_match = None;
+    let mut replaced = String::with_capacity(content.len());
+    let mut edits = vec![];
+    let mut start = 0;
+    for line in content.split('\n') {
+        let range = start..start + line.len();
+        start = range.end + 1;
+        if range.start != 0 {
+            replaced.push('\n');
+        }
+        let processed = processed_lines
+            .iter()
+            .any(|p| p.start <= range.start && range.start <= p.end);
+        let caps = match re.captures(line) {
+            Some(caps) if !processed => caps,
+            // either already processed or not matched
+            _ => {
+                replaced.push_str(line);
+                continue;
+            }
+        };
+        if first_match.is_none() {
+            let mut expanded = String::new();
+            caps.expand(replacement, &mut expanded);
+            first_match = Some((caps[0].to_string(), expanded));
+        }
+        let line = re.replace_all(line, |caps: &regex::Captures| {
+            *matches += 1;
+            let mut expanded = String::new();
+            caps.expand(replacement, &mut expanded);
+            expanded
+        });
+        let new_start = replaced.len();
+        replaced.push_str(&line);
+        edits.push((range, new_start..replaced.len()));
+    }
+    if first_match.is_some() {
+        *content = replaced;
+        shift_processed_lines(processed_lines, &edits);
+        processed_lines.extend(edits.into_iter().map(|(_, new)| new));
+    }
+    first_match
 }
 
+/// Maximum amount of entries kept in the execution log.
+pub const MAX_LOG_ENTRIES: usize = 1000;
+
+/// How often a paused replacement checks whether it was resumed.
+const PAUSE_POLL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// How many substitutions are made between each estimate of the remaining
+/// time of a replacement.
+pub const ETA_ITERATIONS: usize = 10;
+
 pub async fn replace_text(
     original: String,
-    steps_regexes: Vec<Vec<(regex::Regex, String)>>,
+    steps_regexes: Vec<(StepProps, Vec<StepRegex>)>,
     cancel_signal: Arc<AtomicBool>,
-) -> Result<String, (CancelMotive, String)> {
+    pause_signal: Arc<AtomicBool>,
+    limits: ReplacementLimits,
+    callbacks: ReplacementCallbacks,
+    mut log_sender: Sender<LogEntry>,
+) -> Result<ReplacementResult, (CancelMotive, String)> {
     use crc32fast::Hasher;
+    use futures::SinkExt;
     use std::collections::{HashMap, HashSet};
 
     let ms = std::time::Duration::from_millis(1);
+    let started_at = js_sys::Date::now();
+    // time spent paused, or slowed down between regexes, which doesn't count
+    // towards the timeout nor the estimate
+    let mut paused_ms = 0.;
     let original_len = original.len();
     let mut content = original;
     let mut group_count = 0;
-    for step_regexes in &steps_regexes {
+    let mut regex_durations = vec![vec![]; steps_regexes.len()];
+    let mut regex_matches = vec![vec![]; steps_regexes.len()];
+    let total_steps = steps_regexes.len();
+    // whether the latest step changed the content
+    let mut prev_changed = true;
+    // a stable sort, so steps of the same priority keep their declaration order
+    let mut step_order: Vec<StepIndex> = (0..total_steps).collect();
+    step_order.sort_by_key(|&i| steps_regexes[i].0.priority);
+    for (completed_steps, &step_index) in step_order.iter().enumerate() {
+        let (props, step_regexes) = &steps_regexes[step_index];
+        callbacks
+            .progress
+            .emit((completed_steps, total_steps, None));
+        // without a maximum of iterations, the steps are the only measure of
+        // the progress
+        if limits.max_iterations.is_none() && completed_steps > 0 {
+            let elapsed_s = (js_sys::Date::now() - started_at - paused_ms) / 1000.;
+            let remaining_steps = total_steps - completed_steps;
+            callbacks.eta.emit(Eta::About(
+                elapsed_s / completed_steps as f64 * remaining_steps as f64,
+            ));
+        }
+        // indexed by the regex position in the step, which the left out
+        // regexes also count towards
+        let regex_count = step_regexes.iter().map(|r| r.index + 1).max().unwrap_or(0);
+        let mut durations = vec![0.; regex_count];
+        let mut matches = vec![0; regex_count];
+        if !props.enabled {
+            // a disabled step is transparent, so it doesn't affect whether
+            // the previous step changed the content
+            regex_durations[step_index] = durations;
+            regex_matches[step_index] = matches;
+            continue;
+        }
+        if props.run_only_if_prev_changed && !prev_changed {
+            let _ = log_sender
+                .send(LogEntry {
+                    step_index,
+                    regex_index: 0,
+                    iteration: 0,
+                    matched: "".into(),
+                    replacement: "".into(),
+                    skipped: true,
+                })
+                .await;
+            regex_durations[step_index] = durations;
+            regex_matches[step_index] = matches;
+            continue;
+        }
+        let hash_before = crc32fast::hash(content.as_bytes());
+        // the sort keys don't change between restarts, so the order is only
+        // calculated once per step
+        let order = props.virtual_sort.order(step_regexes);
         let mut hash_maps = HashMap::<usize, Option<HashSet<_>>>::new();
         let mut ever_changed = false;
+        let mut iteration = 0;
+        // which regex made the substitution of the previous iteration
+        let mut last_matched: Option<RegexIndex> = None;
+        // lines already substituted by a once-per-line regex
+        let mut processed_lines = vec![];
         loop {
             // check for replacement cycles
             //
@@ -117,20 +788,113 @@ pub async fn replace_text(
                 log::info!("Replacement cancelled.");
                 return Err((CancelMotive::ManuallyCancelled, content));
             }
-            if content.len() > 4 * original_len && content.len() > 1000 {
+            if matches!(limits.deadline, Some(deadline) if js_sys::Date::now() - paused_ms > deadline)
+            {
+                log::warn!("Replacement took too long and thus has been automatically cancelled.");
+                return Err((CancelMotive::Timeout, content));
+            }
+            if content.len() as f64 > limits.growth_factor * original_len as f64
+                && content.len() > 1000
+            {
                 log::warn!("Resulting text is growing too much from the replacement and thus has been automatically cancelled.");
                 return Err((CancelMotive::HighGrowth, content));
             }
             gloo_timers::future::sleep(ms).await;
+            if pause_signal.load(Ordering::SeqCst) {
+                callbacks.paused.emit(content.clone());
+                let paused_at = js_sys::Date::now();
+                // a cancellation also ends the pause
+                while pause_signal.load(Ordering::SeqCst) && !cancel_signal.load(Ordering::SeqCst) {
+                    gloo_timers::future::sleep(PAUSE_POLL).await;
+                }
+                paused_ms += js_sys::Date::now() - paused_at;
+                if cancel_signal.load(Ordering::SeqCst) {
+                    log::info!("Replacement cancelled.");
+                    return Err((CancelMotive::ManuallyCancelled, content));
+                }
+            }
             let mut just_replaced = false;
-            for (re, replacement) in step_regexes {
-                if re.is_match(&content) {
+            for (position, &regex_index) in order.iter().enumerate() {
+                let StepRegex {
+                    index,
+                    title: _,
+                    re,
+                    replacement,
+                    skip_if_prev_no_match,
+                    apply_once_per_line,
+                } = &step_regexes[regex_index];
+                if *skip_if_prev_no_match
+                    && position != 0
+                    && last_matched != Some(order[position - 1])
+                {
+                    continue;
+                }
+                let start = js_sys::Date::now();
+                let first_match = if *apply_once_per_line {
+                    replace_unprocessed_lines(
+                        re,
+                        replacement,
+                        &mut content,
+                        &mut processed_lines,
+                        &mut matches[*index],
+                    )
+                } else {
+                    re.captures(&content).map(|caps| {
+                        let mut expanded = String::new();
+                        caps.expand(replacement, &mut expanded);
+                        (caps[0].to_string(), expanded)
+                    })
+                };
+                if first_match.is_some() && !*apply_once_per_line {
                     // apply the highest priority substitution
-                    content = re.replace_all(&content, replacement).into_owned();
+                    let mut edits = vec![];
+                    // how much longer the content is up to the latest match
+                    let mut growth: isize = 0;
+                    content = re
+                        .replace_all(&content, |caps: &regex::Captures| {
+                            matches[*index] += 1;
+                            let mut expanded = String::new();
+                            caps.expand(replacement, &mut expanded);
+                            let old = caps
+                                .get(0)
+                                .expect("the whole match is always captured")
+                                .range();
+                            let new_start = (old.start as isize + growth) as usize;
+                            growth += expanded.len() as isize - old.len() as isize;
+                            edits.push((old, new_start..new_start + expanded.len()));
+                            expanded
+                        })
+                        .into_owned();
+                    shift_processed_lines(&mut processed_lines, &edits);
+                }
+                durations[*index] += js_sys::Date::now() - start;
+
+                if let Some((matched, replacement)) = first_match {
+                    // the log is best-effort, so a closed log is ignored
+                    let _ = log_sender
+                        .send(LogEntry {
+                            step_index,
+                            regex_index: *index,
+                            iteration,
+                            matched,
+                            replacement,
+                            skipped: false,
+                        })
+                        .await;
 
                     just_replaced = true;
+                    last_matched = Some(regex_index);
                     group_count += 1;
 
+                    if let Some(pause) = props.pause_between_regexes_ms {
+                        callbacks
+                            .progress
+                            .emit((completed_steps, total_steps, Some(*index)));
+                        let slowed_at = js_sys::Date::now();
+                        gloo_timers::future::sleep(std::time::Duration::from_millis(pause)).await;
+                        paused_ms += js_sys::Date::now() - slowed_at;
+                    }
+
                     // allow to restart the step regexes
                     // (allowing higher priorities substitutions)
                     break;
@@ -138,6 +902,32 @@ pub async fn replace_text(
             }
             if just_replaced {
                 ever_changed = true;
+                iteration += 1;
+                // assumes every remaining step runs up to its maximum
+                // iterations, at the rate of the substitutions so far
+                if let Some(max) = limits
+                    .max_iterations
+                    .filter(|_| group_count % ETA_ITERATIONS == 0)
+                {
+                    let elapsed_s = (js_sys::Date::now() - started_at - paused_ms) / 1000.;
+                    if elapsed_s > 0. {
+                        let iterations_per_second = group_count as f64 / elapsed_s;
+                        let remaining_steps = total_steps - completed_steps - 1;
+                        let remaining_iterations =
+                            max.saturating_sub(iteration) + max * remaining_steps;
+                        callbacks.eta.emit(Eta::AtMost(
+                            remaining_iterations as f64 / iterations_per_second,
+                        ));
+                    }
+                }
+                if props.stop_on_first_match {
+                    // only a single substitution is allowed for the step
+                    break;
+                }
+                if matches!(limits.max_iterations, Some(max) if iteration >= max) {
+                    log::info!("Step {} reached its maximum iterations.", step_index + 1);
+                    break;
+                }
                 // restart the step regexes
                 // (allowing higher priorities substitutions)
                 continue;
@@ -147,168 +937,2327 @@ pub async fn replace_text(
                 break;
             }
         }
+        regex_durations[step_index] = durations;
+        regex_matches[step_index] = matches;
+        prev_changed = crc32fast::hash(content.as_bytes()) != hash_before;
         // continue to the next step regexes
     }
-    Ok(content)
+    Ok(ReplacementResult {
+        content,
+        regex_durations,
+        regex_matches,
+    })
 }
 
-impl Component for Model {
-    type Message = Msg;
-    type Properties = ();
+/// Counts how many times each regex of each step matches the input, without
+/// replacing anything.
+pub fn simulate_steps(input: &str, steps_regexes: &[Vec<(Regex, String)>]) -> Vec<Vec<usize>> {
+    steps_regexes
+        .iter()
+        .map(|step_regexes| {
+            step_regexes
+                .iter()
+                .map(|(re, _replacement)| re.find_iter(input).count())
+                .collect()
+        })
+        .collect()
+}
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        let mut steps_edit = IndexSet::new();
-        steps_edit.insert(0);
-        let mut steps = vec![Step::default()];
-        for i in &steps_edit {
-            steps[*i].props.selected = true;
-        }
-        Self {
-            text_projects: vec![TextProject::default()],
-            active_text_project: Some(0),
-            replacement_in_progress: false,
-            replacement_cancel_signal: Arc::new(AtomicBool::new(false)),
-            steps,
-            steps_edit,
-            active_regex_index: None,
+/// Creates a callback for a file input that reads the selected file as text
+/// and sends the message built from it.
+pub fn read_file_callback(
+    link: &yew::html::Scope<Model>,
+    to_msg: impl Fn(String) -> Msg + 'static,
+) -> Callback<Event> {
+    use wasm_bindgen::JsCast;
+
+    let link = link.clone();
+    let to_msg = std::rc::Rc::new(to_msg);
+    Callback::from(move |e: Event| {
+        let input = match e
+            .target()
+            .and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok())
+        {
+            Some(input) => input,
+            None => return,
+        };
+        if let Some(file) = input.files().and_then(|files| files.get(0)) {
+            let file = gloo_file::File::from(file);
+            let link = link.clone();
+            let to_msg = to_msg.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match gloo_file::futures::read_as_text(&file).await {
+                    Ok(text) => link.send_message(to_msg(text)),
+                    Err(err) => log::error!("Failed to read {}: {}", file.name(), err),
+                }
+            });
         }
+        // allows the same file to be selected again
+        input.set_value("");
+    })
+}
+
+/// Reads the text from the clipboard.
+///
+/// The clipboard API is accessed dynamically, as its bindings are still
+/// unstable.
+pub async fn read_clipboard_text() -> Result<String, String> {
+    use wasm_bindgen::JsCast;
+    let window = web_sys::window().ok_or("No window found")?;
+    let clipboard = js_sys::Reflect::get(&window.navigator(), &"clipboard".into())
+        .map_err(|e| format!("{:?}", e))?;
+    if clipboard.is_undefined() {
+        return Err("The clipboard is not available".into());
     }
+    let read_text: js_sys::Function = js_sys::Reflect::get(&clipboard, &"readText".into())
+        .map_err(|e| format!("{:?}", e))?
+        .dyn_into()
+        .map_err(|_| "The clipboard can't be read")?;
+    let promise: js_sys::Promise = read_text
+        .call0(&clipboard)
+        .map_err(|e| format!("{:?}", e))?
+        .dyn_into()
+        .map_err(|_| "The clipboard can't be read")?;
+    let text = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    text.as_string()
+        .ok_or_else(|| "The clipboard has no text".into())
+}
 
-    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
-            Msg::AddStep => {
-                if self.replacement_in_progress {
-                    log::warn!(
-                        "Added step won't affect the replacement that is already in progress."
-                    );
-                }
-                self.steps.push(Step::default());
-                true
-            }
-            Msg::SelectStep(index) => {
-                if self.steps_edit.contains(&index) {
-                    self.steps_edit.remove(&index);
-                    self.steps[index].props.selected = false;
-                    true
-                } else {
-                    self.steps_edit.insert(index);
-                    self.steps[index].props.selected = true;
-                    true
-                }
-            }
-            Msg::SetStepEnabled(index, value) => {
-                if self.replacement_in_progress {
-                    log::warn!(
-                        "Modified step won't affect the replacement that is already in progress."
-                    );
-                }
-                self.steps[index].props.enabled = value;
-                true
+/// Places the input highlight overlay exactly over the input textarea, with
+/// the same text metrics and scroll position.
+pub fn align_input_highlight() {
+    use wasm_bindgen::JsCast;
+
+    let document = match web_sys::window().and_then(|w| w.document()) {
+        Some(document) => document,
+        None => return,
+    };
+    let overlay = document
+        .get_element_by_id("input-highlight")
+        .and_then(|e| e.dyn_into::<web_sys::HtmlElement>().ok());
+    let textarea = document
+        .query_selector("textarea[name='original-text']")
+        .ok()
+        .flatten();
+    let (overlay, textarea) = match (overlay, textarea) {
+        (Some(overlay), Some(textarea)) => (overlay, textarea),
+        _ => return,
+    };
+    let parent = match overlay.offset_parent() {
+        Some(parent) => parent,
+        None => return,
+    };
+
+    let rect = textarea.get_bounding_client_rect();
+    let parent_rect = parent.get_bounding_client_rect();
+    let style = overlay.style();
+    let mut properties = vec![
+        ("top", format!("{}px", rect.top() - parent_rect.top())),
+        ("left", format!("{}px", rect.left() - parent_rect.left())),
+        ("width", format!("{}px", rect.width())),
+        ("height", format!("{}px", rect.height())),
+    ];
+    let computed = web_sys::window().and_then(|w| w.get_computed_style(&textarea).ok().flatten());
+    if let Some(computed) = computed {
+        for name in [
+            "font-family",
+            "font-size",
+            "line-height",
+            "letter-spacing",
+            "padding-top",
+            "padding-right",
+            "padding-bottom",
+            "padding-left",
+            "border-top-width",
+            "border-right-width",
+            "border-bottom-width",
+            "border-left-width",
+            "white-space",
+            "direction",
+        ] {
+            if let Ok(value) = computed.get_property_value(name) {
+                properties.push((name, value));
             }
-            Msg::UpdateStepTitle(step_index, title) => {
-                self.steps[step_index].props.title = title;
-                true
+        }
+    }
+    for (name, value) in properties {
+        let _ = style.set_property(name, &value);
+    }
+    overlay.set_scroll_top(textarea.scroll_top());
+    overlay.set_scroll_left(textarea.scroll_left());
+}
+
+/// Wraps the selected text of the named input, or textarea, between `before`
+/// and `after`, returning its new value.
+///
+/// Without a selection, the wrapping is inserted at the caret.
+pub fn wrap_input_selection(name: &str, before: &str, after: &str) -> Option<String> {
+    use wasm_bindgen::JsCast;
+
+    let element = web_sys::window()?
+        .document()?
+        .query_selector(&format!("[name='{}']", name))
+        .ok()??;
+    // the end is wrapped first, so the start position is still valid
+    let (wrapped, value) = if let Some(textarea) = element.dyn_ref::<web_sys::HtmlTextAreaElement>()
+    {
+        let start = textarea.selection_start().ok()??;
+        let end = textarea.selection_end().ok()??;
+        let wrapped = textarea
+            .set_range_text_with_start_and_end(after, end, end)
+            .and_then(|_| textarea.set_range_text_with_start_and_end(before, start, start));
+        (wrapped, textarea.value())
+    } else {
+        let input = element.dyn_ref::<web_sys::HtmlInputElement>()?;
+        let start = input.selection_start().ok()??;
+        let end = input.selection_end().ok()??;
+        let wrapped = input
+            .set_range_text_with_start_and_end(after, end, end)
+            .and_then(|_| input.set_range_text_with_start_and_end(before, start, start));
+        (wrapped, input.value())
+    };
+    if let Err(err) = wrapped {
+        log::error!("Failed to wrap the selection: {:?}", err);
+        return None;
+    }
+    Some(value)
+}
+
+/// Identifies a textarea that has line numbers.
+pub enum TextAreaId {
+    Input,
+    Output,
+}
+
+/// Renders the line numbers of the text, scrolled along with its textarea.
+///
+/// Each line of the text gets a single number, so the numbers only line up
+/// with the textarea when its long lines don't wrap, and they're only
+/// rendered then.
+pub fn render_line_numbers(text: &str, scroll_top: f64, font_size: FontSize) -> Html {
+    let lines = text.split('\n').count();
+    html! {
+        <div class={classes!("line-numbers", font_size.class())} aria-hidden="true">
+            <div style={format!("transform: translateY(-{}px);", scroll_top)}>
+                { for (1..=lines).map(|n| html! {<span>{n}</span>}) }
+            </div>
+        </div>
+    }
+}
+
+/// Links the control of each field to the help text below it, with
+/// `aria-describedby`, as the fields don't expose that attribute.
+///
+/// The help is identified by the control name, so controls without a name
+/// are left alone.
+pub fn describe_fields_by_help(document: &web_sys::Document) {
+    use wasm_bindgen::JsCast;
+
+    let fields = match document.query_selector_all(".field") {
+        Ok(fields) => fields,
+        Err(_) => return,
+    };
+    for k in 0..fields.length() {
+        let field = match fields
+            .item(k)
+            .and_then(|f| f.dyn_into::<web_sys::Element>().ok())
+        {
+            Some(field) => field,
+            None => continue,
+        };
+        let help = field.query_selector(":scope > .help").ok().flatten();
+        let control = field
+            .query_selector("input, textarea, select")
+            .ok()
+            .flatten();
+        let (help, control) = match (help, control) {
+            (Some(help), Some(control)) => (help, control),
+            _ => continue,
+        };
+        let name = match control.get_attribute("name") {
+            Some(name) => name,
+            None => continue,
+        };
+        if help.text_content().unwrap_or_default().trim().is_empty() {
+            let _ = control.remove_attribute("aria-describedby");
+            continue;
+        }
+        let id = format!("{}-help", name);
+        help.set_id(&id);
+        let _ = control.set_attribute("aria-describedby", &id);
+    }
+}
+
+/// Shows the notification to the user, and writes it to the browser console.
+pub fn notify(ctx: &Context<Model>, notification: Notification) {
+    ctx.link().send_message(Msg::AddNotification(notification));
+}
+
+/// The Bulma color of a tag's pill, which is the same for equal tags.
+pub fn tag_color(tag: &str) -> &'static str {
+    const COLORS: &[&str] = &[
+        "is-primary",
+        "is-link",
+        "is-info",
+        "is-success",
+        "is-warning",
+        "is-danger",
+    ];
+    let hash = tag
+        .bytes()
+        .fold(0usize, |h, b| h.wrapping_mul(31).wrapping_add(b as usize));
+    COLORS[hash % COLORS.len()]
+}
+
+/// Where the item at position `i` ends up after the item at `from` is moved
+/// to `to`, shifting the ones in between.
+pub fn moved_index(i: usize, from: usize, to: usize) -> usize {
+    match i {
+        i if i == from => to,
+        i if from < i && i <= to => i - 1,
+        i if to <= i && i < from => i + 1,
+        i => i,
+    }
+}
+
+/// Splits the text at line boundaries into chunks of approximately
+/// `chunk_size` bytes.
+pub fn split_into_chunks(text: &str, chunk_size: usize) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut chunk = String::new();
+    for line in text.split_inclusive('\n') {
+        chunk.push_str(line);
+        if chunk.len() >= chunk_size {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+    }
+    if !chunk.is_empty() || chunks.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+impl Model {
+    /// Renders the output search bar and a preview of the output with every
+    /// match highlighted.
+    pub fn render_output_search(&self, ctx: &Context<Self>, output: &str) -> Html {
+        let link = ctx.link();
+        let search = match &self.output_search {
+            Some(search) => search,
+            None => return html! {},
+        };
+        let ranges = search.find_ranges(output);
+        let current = search.current.min(ranges.len().saturating_sub(1));
+
+        let mut preview = vec![];
+        let mut last = 0;
+        for (k, (start, end)) in ranges.iter().cloned().enumerate() {
+            preview.push(html! {{&output[last..start]}});
+            if k == current {
+                preview.push(html! {
+                    <mark id="output-search-current" class="has-background-warning">{&output[start..end]}</mark>
+                });
+            } else {
+                preview.push(html! {<mark>{&output[start..end]}</mark>});
             }
-            Msg::AddRegex(step_index) => {
-                if self.replacement_in_progress {
-                    log::warn!(
-                        "Added regex won't affect the replacement that is already in progress."
-                    );
+            last = end;
+        }
+        preview.push(html! {{&output[last..]}});
+
+        html! {
+            <div class="mt-2">
+                <ybc::Field grouped=true>
+                    <ybc::Control expanded=true>
+                    <ybc::Input
+                        name={"output-search"}
+                        value={search.query.clone()}
+                        update={link.callback(Msg::UpdateOutputSearch)}
+                        placeholder={"Search in the result.."}
+                        size={ybc::Size::Small}
+                    />
+                    </ybc::Control>
+                    <ybc::Control>
+                        <ybc::Checkbox
+                            name={"output-search-case-insensitive"}
+                            checked={search.case_insensitive}
+                            update={link.callback(Msg::SetOutputSearchCaseInsensitive)}
+                        >
+                            {" Case insensitive"}
+                        </ybc::Checkbox>
+                    </ybc::Control>
+                    <ybc::Control>
+                        <span class="is-size-7">
+                            {if ranges.is_empty() {
+                                "0 matches".to_string()
+                            } else {
+                                format!("{} / {} matches", current + 1, ranges.len())
+                            }}
+                        </span>
+                    </ybc::Control>
+                    <ybc::Control>
+                        <a onclick={link.callback(|_| Msg::MoveOutputSearch(MoveDirection::Up))}><ybc::Button
+                            classes={classes!("is-small")}
+                            disabled={ranges.is_empty()}
+                        >
+                            <span class="icon is-small">
+                                <i class="fas fa-arrow-up" aria-hidden="true"></i>
+                            </span>
+                            <span class="is-sr-only">{"Previous match"}</span>
+                        </ybc::Button></a>
+                        <a onclick={link.callback(|_| Msg::MoveOutputSearch(MoveDirection::Down))}><ybc::Button
+                            classes={classes!("is-small")}
+                            disabled={ranges.is_empty()}
+                        >
+                            <span class="icon is-small">
+                                <i class="fas fa-arrow-down" aria-hidden="true"></i>
+                            </span>
+                            <span class="is-sr-only">{"Next match"}</span>
+                        </ybc::Button></a>
+                    </ybc::Control>
+                </ybc::Field>
+                if !search.query.is_empty() {
+                    <pre class="output-search-preview">{ for preview }</pre>
                 }
-                self.steps[step_index].regexes.push(RegexInfo::default());
-                true
-            }
-            Msg::UpdateRegexTitle(step_index, regex_index, title) => {
-                self.steps[step_index].regexes[regex_index].title = title;
-                true
+            </div>
+        }
+    }
+
+    /// Replaces the first, or all, occurrences of `find` in the input of the
+    /// active text project.
+    pub fn replace_in_input(&mut self, find: &str, replace: &str, all: bool) -> bool {
+        if self.replacement_in_progress {
+            log::error!("A replacement is already in progress.");
+            return false;
+        }
+        let project_index = match self.active_text_project {
+            Some(i) => i,
+            None => return false,
+        };
+        let project = &mut self.text_projects[project_index];
+        if find.is_empty() || project.input.find(find).is_none() {
+            return false;
+        }
+        project.input = if all {
+            project.input.replace(find, replace)
+        } else {
+            project.input.replacen(find, replace, 1)
+        };
+        project.output_status = OutputStatus::Outdated;
+        self.update_match_counts();
+        self.update_highlighted_ranges();
+        true
+    }
+
+    /// Clears the execution log and creates a sender whose entries are
+    /// appended to it.
+    pub fn start_execution_log(&mut self, ctx: &Context<Self>) -> Sender<LogEntry> {
+        use futures::StreamExt;
+
+        self.execution_log.clear();
+        let (log_sender, mut log_receiver) = futures::channel::mpsc::channel(64);
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            while let Some(entry) = log_receiver.next().await {
+                link.send_message(Msg::AppendLog(entry));
             }
-            Msg::UpdateRegexSearch(step_index, regex_index, search) => {
-                if self.replacement_in_progress {
-                    log::warn!(
-                        "Changed regex won't affect the replacement that is already in progress."
-                    );
-                }
-                let r = &mut self.steps[step_index].regexes[regex_index];
-                match Regex::new(&search) {
-                    Ok(re) => {
-                        r.r#match = Ok(re);
-                        r.match_parse_error = None;
-                        true
-                    }
-                    Err(err) => {
-                        r.r#match = Err(search);
-                        r.match_parse_error = Some(err);
-                        true
-                    }
+        });
+        log_sender
+    }
+
+    /// Starts the auto-run that was waiting for the replacement in progress
+    /// to end, if any.
+    pub fn restart_pending_auto_run(&mut self, ctx: &Context<Self>) {
+        self.input_edited_during_run = false;
+        if let Some(project_index) = self.auto_run_restart.take() {
+            ctx.link()
+                .send_message(Msg::StartReplacingText(Some(project_index)));
+        }
+    }
+
+    /// The limits of a replacement that starts now.
+    pub fn replacement_limits(&self) -> ReplacementLimits {
+        ReplacementLimits {
+            deadline: self
+                .settings
+                .global_timeout_ms
+                .map(|timeout_ms| js_sys::Date::now() + timeout_ms as f64),
+            growth_factor: self.settings.growth_factor,
+            max_iterations: self.settings.max_iterations_default,
+        }
+    }
+
+    /// Titles of the enabled steps, kept along with archived outputs.
+    pub fn step_snapshot(&self) -> Vec<String> {
+        self.steps
+            .iter()
+            .filter(|step| step.props.enabled)
+            .map(|step| {
+                if step.props.title.trim().is_empty() {
+                    "New Step".to_string()
+                } else {
+                    step.props.title.clone()
                 }
+            })
+            .collect()
+    }
+
+    /// Renders the global settings, if they're open.
+    pub fn render_settings(&self, ctx: &Context<Self>) -> Html {
+        if !self.settings_open {
+            return html! {};
+        }
+        let link = ctx.link();
+        let close = link.callback(|_| Msg::CloseSettings);
+        let settings = &self.settings;
+        let update = |f: fn(&mut GlobalSettings, String)| {
+            let settings = settings.clone();
+            link.callback(move |value: String| {
+                let mut settings = settings.clone();
+                f(&mut settings, value);
+                Msg::UpdateSettings(settings)
+            })
+        };
+        let draft = &self.settings_draft;
+        let update_draft = |f: fn(&mut SettingsDraft, String)| {
+            let draft = draft.clone();
+            link.callback(move |value: String| {
+                let mut draft = draft.clone();
+                f(&mut draft, value);
+                Msg::UpdateSettingsDraft(draft)
+            })
+        };
+        let set_auto_run = {
+            let settings = settings.clone();
+            link.callback(move |auto_run| {
+                Msg::UpdateSettings(GlobalSettings {
+                    auto_run,
+                    ..settings.clone()
+                })
+            })
+        };
+        let set_tab_indent = {
+            let settings = settings.clone();
+            link.callback(move |tab_indent| {
+                Msg::UpdateSettings(GlobalSettings {
+                    tab_indent,
+                    ..settings.clone()
+                })
+            })
+        };
+        let set_high_contrast = {
+            let settings = settings.clone();
+            link.callback(move |high_contrast| {
+                Msg::UpdateSettings(GlobalSettings {
+                    high_contrast,
+                    ..settings.clone()
+                })
+            })
+        };
+        html! {
+            <div class="modal is-active" role="dialog" aria-modal="true">
+                <div class="modal-background" onclick={close.clone()}></div>
+                <div class="modal-card">
+                    <header class="modal-card-head">
+                        <p class="modal-card-title">{"Settings"}</p>
+                        <ybc::Delete tag={"button"} onclick={close} />
+                    </header>
+                    <section class="modal-card-body">
+                        <ybc::Field>
+                            <ybc::Checkbox
+                                name={"settings-auto-run"}
+                                checked={settings.auto_run}
+                                update={set_auto_run}
+                            >
+                                {" Run the replacement whenever the original text changes"}
+                            </ybc::Checkbox>
+                        </ybc::Field>
+                        <ybc::Field help={"Otherwise, Tab moves the focus to the next field."}>
+                            <ybc::Checkbox
+                                name={"settings-tab-indent"}
+                                checked={settings.tab_indent}
+                                update={set_tab_indent}
+                            >
+                                {" Tab indents the original text"}
+                            </ybc::Checkbox>
+                        </ybc::Field>
+                        <ybc::Field help={"Can be combined with the dark theme."}>
+                            <ybc::Checkbox
+                                name={"settings-high-contrast"}
+                                checked={settings.high_contrast}
+                                update={set_high_contrast}
+                            >
+                                {" High contrast"}
+                            </ybc::Checkbox>
+                        </ybc::Field>
+                        <div onfocusout={link.callback(|_| Msg::ApplySettingsDraft)}>
+                        <ybc::Field
+                            label={"Replacement timeout (ms)"}
+                            help={"A replacement that runs for longer is cancelled, keeping its partial result. Leave empty for no timeout."}
+                        >
+                            <ybc::Input
+                                name={"settings-global-timeout"}
+                                value={draft.global_timeout_ms.clone()}
+                                update={update_draft(|d, value| d.global_timeout_ms = value)}
+                                placeholder={"No timeout"}
+                            />
+                        </ybc::Field>
+                        <ybc::Field
+                            label={"Growth factor"}
+                            help={"A replacement is cancelled once the text is this many times longer than the original text."}
+                        >
+                            <ybc::Input
+                                name={"settings-growth-factor"}
+                                value={draft.growth_factor.clone()}
+                                update={update_draft(|d, value| d.growth_factor = value)}
+                            />
+                        </ybc::Field>
+                        <ybc::Field
+                            label={"Maximum regexes per step"}
+                            help={"Regexes can't be added to a step that has this many. Leave empty for no limit."}
+                        >
+                            <ybc::Input
+                                name={"settings-max-regexes-per-step"}
+                                value={draft.max_regexes_per_step.clone()}
+                                update={update_draft(|d, value| d.max_regexes_per_step = value)}
+                                placeholder={"No limit"}
+                            />
+                        </ybc::Field>
+                        <ybc::Field
+                            label={"Maximum iterations per step"}
+                            help={"A step moves on to the next step after this many substitutions. Leave empty for no limit."}
+                        >
+                            <ybc::Input
+                                name={"settings-max-iterations"}
+                                value={draft.max_iterations_default.clone()}
+                                update={update_draft(|d, value| d.max_iterations_default = value)}
+                                placeholder={"No limit"}
+                            />
+                        </ybc::Field>
+                        </div>
+                        <ybc::Field grouped=true>
+                            <ybc::Field label={"Theme"}>
+                            <ybc::Control>
+                            <ybc::Select
+                                name={"settings-theme"}
+                                value={Theme::OPTIONS
+                                    .iter()
+                                    .position(|(theme, _)| *theme == settings.theme)
+                                    .unwrap_or_default()
+                                    .to_string()}
+                                update={update(|s, value| {
+                                    if let Some((theme, _)) = value.parse().ok().and_then(|k: usize| Theme::OPTIONS.get(k)) {
+                                        s.theme = *theme;
+                                    }
+                                })}
+                            >
+                                { for Theme::OPTIONS.iter().enumerate().map(|(k, (theme, label))| html! {
+                                    <option value={k.to_string()} selected={*theme == settings.theme}>
+                                        {label}
+                                    </option>
+                                })}
+                            </ybc::Select>
+                            </ybc::Control>
+                            </ybc::Field>
+                            <ybc::Field label={"Font size"} classes={classes!("ml-3")}>
+                            <ybc::Control>
+                            <ybc::Select
+                                name={"settings-font-size"}
+                                value={FontSize::OPTIONS
+                                    .iter()
+                                    .position(|(font_size, _)| *font_size == settings.font_size)
+                                    .unwrap_or_default()
+                                    .to_string()}
+                                update={update(|s, value| {
+                                    if let Some((font_size, _)) = value.parse().ok().and_then(|k: usize| FontSize::OPTIONS.get(k)) {
+                                        s.font_size = *font_size;
+                                    }
+                                })}
+                            >
+                                { for FontSize::OPTIONS.iter().enumerate().map(|(k, (font_size, label))| html! {
+                                    <option value={k.to_string()} selected={*font_size == settings.font_size}>
+                                        {label}
+                                    </option>
+                                })}
+                            </ybc::Select>
+                            </ybc::Control>
+                            </ybc::Field>
+                        </ybc::Field>
+                    </section>
+                </div>
+            </div>
+        }
+    }
+
+    /// Renders the table of common regex constructs, if it's open.
+    pub fn render_cheatsheet(&self, ctx: &Context<Self>) -> Html {
+        if !self.cheatsheet_open {
+            return html! {};
+        }
+        let link = ctx.link();
+        let close = link.callback(|_| Msg::ToggleCheatsheet);
+        html! {
+            <div class="modal is-active" role="dialog" aria-modal="true">
+                <div class="modal-background" onclick={close.clone()}></div>
+                <div class="modal-card">
+                    <header class="modal-card-head">
+                        <p class="modal-card-title">{"Regex Cheatsheet"}</p>
+                        <ybc::Delete tag={"button"} onclick={close} />
+                    </header>
+                    <section class="modal-card-body">
+                        <p class="is-size-7 mb-3">
+                            {"Insert appends the construct to the latest focused regex match."}
+                        </p>
+                        <table class="table is-narrow is-fullwidth">
+                            <tbody>
+                            { for cheatsheet::CHEATSHEET.iter().map(|(construct, description)| html! {
+                                <tr>
+                                    <td><code>{construct}</code></td>
+                                    <td>{description}</td>
+                                    <td>
+                                        <a onclick={link.callback(move |_| Msg::InsertIntoRegex(construct))}><ybc::Button
+                                            classes={classes!("is-small")}
+                                            disabled={self.last_focused_regex.is_none()}
+                                        >
+                                            {"Insert"}
+                                        </ybc::Button></a>
+                                    </td>
+                                </tr>
+                            })}
+                            </tbody>
+                        </table>
+                    </section>
+                </div>
+            </div>
+        }
+    }
+
+    /// Renders the editor of the replacement variables, if it's open.
+    pub fn render_variables(&self, ctx: &Context<Self>) -> Html {
+        if !self.variables_open {
+            return html! {};
+        }
+        let close = ctx.link().callback(|_| Msg::ShowVariables(false));
+        html! {
+            <div class="modal is-active" role="dialog" aria-modal="true">
+                <div class="modal-background" onclick={close.clone()}></div>
+                <div class="modal-card">
+                    <header class="modal-card-head">
+                        <p class="modal-card-title">{"Variables"}</p>
+                        <ybc::Delete tag={"button"} onclick={close} />
+                    </header>
+                    <section class="modal-card-body">
+                        <p class="is-size-7 mb-3">
+                            {"Replacements can reference a variable as "}
+                            <code>{"$NAME"}</code>{" or "}<code>{"${NAME}"}</code>
+                            {". A variable takes precedence over a capture group of the same name."}
+                        </p>
+                        {self.render_variables_editor(ctx, None)}
+                    </section>
+                </div>
+            </div>
+        }
+    }
+
+    /// Renders a row to edit each variable, followed by a row to add a new
+    /// one.
+    ///
+    /// Edits the local variables of the project, if any, or else the global
+    /// variables.
+    pub fn render_variables_editor(
+        &self,
+        ctx: &Context<Self>,
+        project_index: Option<ProjectIndex>,
+    ) -> Html {
+        let link = ctx.link();
+        let (variables, prefix) = match project_index {
+            Some(i) => (&self.text_projects[i].local_variables, "project-"),
+            None => (&self.variables, ""),
+        };
+        let (new_name, new_value) = self.new_variable.written_on(project_index);
+        let new_name = new_name.trim();
+        let new_name_error = if new_name.is_empty() || variables::is_valid_name(new_name) {
+            None
+        } else {
+            Some("Names must start with a letter or `_`, followed by letters, digits or `_`.")
+        };
+        let add_variable = link.callback(move |_| match project_index {
+            Some(i) => Msg::AddProjectVariable(i),
+            None => Msg::AddVariable,
+        });
+        html! {
+            <>
+            { for variables.iter().map(|(name, value)| {
+                let set_name = name.clone();
+                let delete_name = name.clone();
+                let set_variable = link.callback(move |v| match project_index {
+                    Some(i) => Msg::SetProjectVariable(i, set_name.clone(), v),
+                    None => Msg::SetVariable(set_name.clone(), v),
+                });
+                let delete_variable = link.callback(move |_| match project_index {
+                    Some(i) => Msg::DeleteProjectVariable(i, delete_name.clone()),
+                    None => Msg::DeleteVariable(delete_name.clone()),
+                });
+                html! {
+                    <ybc::Field grouped=true>
+                        <ybc::Control>
+                            <ybc::Input
+                                name={format!("{}variable-{}-name", prefix, name)}
+                                value={format!("${}", name)}
+                                update={Callback::noop()}
+                                size={ybc::Size::Small}
+                                readonly=true
+                                r#static=true
+                            />
+                        </ybc::Control>
+                        <ybc::Control expanded=true>
+                            <ybc::Input
+                                name={format!("{}variable-{}-value", prefix, name)}
+                                value={value.clone()}
+                                update={set_variable}
+                                size={ybc::Size::Small}
+                            />
+                        </ybc::Control>
+                        <ybc::Control>
+                            <a onclick={delete_variable}><ybc::Button classes={classes!("is-small")}>
+                                <span class="icon is-small">
+                                    <i class="fas fa-trash" aria-hidden="true"></i>
+                                </span>
+                                <span class="is-sr-only">{"Delete the variable"}</span>
+                            </ybc::Button></a>
+                        </ybc::Control>
+                    </ybc::Field>
+                }
+            })}
+            <ybc::Field
+                grouped=true
+                help={new_name_error.map(String::from)}
+                help_has_error=true
+            >
+                <ybc::Control>
+                    <ybc::Input
+                        name={format!("{}new-variable-name", prefix)}
+                        value={new_name.to_string()}
+                        update={link.callback(move |name| Msg::UpdateNewVariableName(project_index, name))}
+                        placeholder={"NAME"}
+                        size={ybc::Size::Small}
+                    />
+                </ybc::Control>
+                <ybc::Control expanded=true>
+                    <ybc::Input
+                        name={format!("{}new-variable-value", prefix)}
+                        value={new_value.to_string()}
+                        update={link.callback(move |value| Msg::UpdateNewVariableValue(project_index, value))}
+                        placeholder={"Value"}
+                        size={ybc::Size::Small}
+                    />
+                </ybc::Control>
+                <ybc::Control>
+                    <a onclick={add_variable}><ybc::Button
+                        classes={classes!("is-small")}
+                        disabled={new_name.is_empty() || new_name_error.is_some()}
+                    >
+                        {"Add"}
+                    </ybc::Button></a>
+                </ybc::Control>
+            </ybc::Field>
+            </>
+        }
+    }
+
+    /// Updates the input ranges matched by the highlighted regex.
+    ///
+    /// The ranges are cleared if there is no highlighted regex or if it's not
+    /// valid.
+    pub fn update_highlighted_ranges(&mut self) {
+        let input = self
+            .active_text_project
+            .map(|i| &self.text_projects[i].input);
+        let re = self
+            .highlighted_regex
+            .and_then(|(i, j)| self.steps.get(i).and_then(|step| step.regexes.get(j)))
+            .and_then(|r| r.r#match.as_ref().ok());
+        self.highlighted_ranges = match (input, re) {
+            (Some(input), Some(re)) => re
+                .find_iter(input)
+                .filter(|m| !m.range().is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+            _ => vec![],
+        };
+    }
+
+    /// Updates the match count of every regex against the input of the
+    /// active text project, and whether it matches that input at all.
+    pub fn update_match_counts(&mut self) {
+        let input = match self.active_text_project {
+            Some(i) => &self.text_projects[i].input,
+            None => {
+                for r in self.steps.iter_mut().flat_map(|s| s.regexes.iter_mut()) {
+                    r.match_count = None;
+                    r.has_match_on_input = None;
+                }
+                return;
+            }
+        };
+
+        for r in self.steps.iter_mut().flat_map(|s| s.regexes.iter_mut()) {
+            r.has_match_on_input = r.r#match.as_ref().ok().map(|re| re.is_match(input));
+        }
+
+        let mut indexes = vec![];
+        let mut steps_regexes = vec![];
+        for step in self.steps.iter() {
+            let mut indexes_i = vec![];
+            let mut regexes_i = vec![];
+            for (j, r) in step.regexes.iter().enumerate() {
+                if let Ok(re) = &r.r#match {
+                    indexes_i.push(j);
+                    regexes_i.push((re.clone(), r.replace.clone()));
+                }
+            }
+            indexes.push(indexes_i);
+            steps_regexes.push(regexes_i);
+        }
+
+        let counts = simulate_steps(input, &steps_regexes);
+        for ((step, indexes_i), counts_i) in self.steps.iter_mut().zip(indexes).zip(counts) {
+            for r in step.regexes.iter_mut() {
+                r.match_count = None;
+            }
+            for (j, count) in indexes_i.into_iter().zip(counts_i) {
+                step.regexes[j].match_count = Some(count);
+            }
+        }
+    }
+
+    /// Collects the compiled regexes and their replacements of every step,
+    /// with the variables of the project expanded.
+    ///
+    /// Regexes with an empty match are ignored, and `None` is returned if any
+    /// regex has a parse error.
+    pub fn collect_steps_regexes(
+        &self,
+        project_index: ProjectIndex,
+    ) -> Option<Vec<(StepProps, Vec<StepRegex>)>> {
+        let mut regexes = vec![];
+        // local variables shadow the global ones
+        let mut variables = self.variables.clone();
+        for (name, value) in self.text_projects[project_index].local_variables.iter() {
+            variables.insert(name.clone(), value.clone());
+        }
+
+        for step in self.steps.iter() {
+            let mut regexes_i = vec![];
+
+            for (index, re) in step.regexes.iter().enumerate().filter(|(_, re)| re.enabled) {
+                let r#match = match &re.r#match {
+                    Ok(r) => r,
+                    Err(s) if s.is_empty() => {
+                        continue;
+                    }
+                    Err(s) => {
+                        log::error!("The regex {} had a parse error", s);
+                        return None;
+                    }
+                };
+                let repl = variables::expand_variables(&re.replace, &variables);
+                regexes_i.push(StepRegex {
+                    index,
+                    title: re.title.clone(),
+                    re: r#match.clone(),
+                    replacement: repl,
+                    skip_if_prev_no_match: re.skip_if_prev_no_match,
+                    apply_once_per_line: re.apply_once_per_line,
+                });
+            }
+            regexes.push((step.props.clone(), regexes_i));
+        }
+        Some(regexes)
+    }
+
+    /// Whether `count` more regexes fit in the step, under the maximum of
+    /// regexes per step, notifying the user if they don't.
+    pub fn fits_more_regexes(
+        &self,
+        ctx: &Context<Self>,
+        step_index: StepIndex,
+        count: usize,
+    ) -> bool {
+        let max = match self.settings.max_regexes_per_step {
+            Some(max) => max,
+            None => return true,
+        };
+        let len = self.steps[step_index].regexes.len();
+        if len + count <= max {
+            return true;
+        }
+        notify(
+            ctx,
+            Notification::warning(format!(
+                "Step {} can't have more than {} regexes.",
+                step_index + 1,
+                max
+            )),
+        );
+        false
+    }
+
+    /// Re-maps the indexes of the steps that are opened for edit.
+    ///
+    /// Steps that are mapped into `None` are closed.
+    pub fn remap_steps_edit(&mut self, f: impl Fn(StepIndex) -> Option<StepIndex>) {
+        self.steps_edit = self.steps_edit.iter().filter_map(|&i| f(i)).collect();
+    }
+
+    /// Re-maps the positions of the regexes that are referred to, such as the
+    /// active one, after regexes or steps were moved or removed.
+    ///
+    /// Positions that map to `None` are forgotten.
+    pub fn remap_regex_positions(
+        &mut self,
+        f: impl Fn(StepIndex, RegexIndex) -> Option<(StepIndex, RegexIndex)>,
+    ) {
+        for position in [
+            &mut self.active_regex,
+            &mut self.last_focused_regex,
+            &mut self.highlighted_regex,
+            &mut self.regex_diagram,
+            &mut self.scroll_to_regex,
+        ] {
+            *position = position.and_then(|(i, j)| f(i, j));
+        }
+        if self.highlighted_regex.is_none() {
+            self.highlighted_ranges.clear();
+        }
+        self.new_group_name = self
+            .new_group_name
+            .take()
+            .and_then(|((i, j), name)| Some((f(i, j)?, name)));
+    }
+}
+
+impl Component for Model {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        let mut steps_edit = IndexSet::new();
+        steps_edit.insert(0);
+        let mut steps = vec![Step::default()];
+        for i in &steps_edit {
+            steps[*i].props.selected = true;
+        }
+        let settings = GlobalSettings::load();
+        // the steps are not persisted, so every page load is a fresh start,
+        // which demonstrates a replacement until the welcome is dismissed
+        if !settings.welcome_dismissed {
+            steps[0]
+                .regexes
+                .push(RegexInfo::new("".into(), "hello".into(), "world".into()));
+        }
+        Self {
+            text_projects: vec![TextProject::default()],
+            active_text_project: Some(0),
+            replacement_in_progress: false,
+            auto_run_restart: None,
+            input_edited_during_run: false,
+            replacement_cancel_signal: Arc::new(AtomicBool::new(false)),
+            pause_signal: Arc::new(AtomicBool::new(false)),
+            replacement_eta: None,
+            ab_progress: None,
+            execution_log: vec![],
+            execution_log_open: false,
+            output_search: None,
+            scroll_to_output_search: false,
+            scroll_to_regex: None,
+            input_collapsed: false,
+            input_toolbar_open: false,
+            input_find: String::new(),
+            input_replace: String::new(),
+            steps,
+            steps_edit,
+            active_regex_index: None,
+            regex_diagram: None,
+            highlighted_regex: None,
+            highlighted_ranges: vec![],
+            last_focused_regex: None,
+            active_regex: None,
+            cheatsheet_open: false,
+            steps_dropdown_expanded: false,
+            split_delimiter: r"\n---\n".into(),
+            new_tag: String::new(),
+            tag_filter: None,
+            new_group_name: None,
+            regex_search_query: String::new(),
+            screen_reader_message: String::new(),
+            input_scroll_listener: None,
+            output_scroll_listener: None,
+            input_scroll_top: 0.,
+            output_scroll_top: 0.,
+            pending_confirmation: None,
+            notifications: VecDeque::new(),
+            current_run: None,
+            run_history: vec![],
+            stats_open: false,
+            settings,
+            settings_open: false,
+            settings_draft: SettingsDraft::default(),
+            variables: IndexMap::new(),
+            variables_open: false,
+            project_variables_open: false,
+            new_variable: NewVariable::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::AddStep => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Added step won't affect the replacement that is already in progress."
+                    );
+                }
+                // new steps run after the existing ones, as they are inserted
+                let mut step = Step::default();
+                step.props.priority = self.steps.len();
+                self.steps.push(step);
+                true
+            }
+            Msg::SelectStep(index) => {
+                if self.steps_edit.contains(&index) {
+                    self.steps_edit.remove(&index);
+                    self.steps[index].props.selected = false;
+                    true
+                } else {
+                    self.steps_edit.insert(index);
+                    self.steps[index].props.selected = true;
+                    true
+                }
+            }
+            Msg::OpenStepEditor(index) => {
+                if !self.steps_edit.insert(index) {
+                    return false;
+                }
+                self.steps[index].props.selected = true;
+                true
+            }
+            Msg::JumpToRegex(step_index, regex_index) => {
+                let step = match self.steps.get_mut(step_index) {
+                    Some(step) if regex_index < step.regexes.len() => step,
+                    _ => {
+                        log::warn!(
+                            "There is no regex {} in step {}.",
+                            regex_index + 1,
+                            step_index + 1
+                        );
+                        return false;
+                    }
+                };
+                step.props.selected = true;
+                self.steps_edit.insert(step_index);
+                self.scroll_to_regex = Some((step_index, regex_index));
+                true
+            }
+            Msg::SetActiveRegex(step_index, regex_index) => {
+                if self.active_regex == Some((step_index, regex_index)) {
+                    self.active_regex = None;
+                } else {
+                    self.active_regex = Some((step_index, regex_index));
+                }
+                true
+            }
+            Msg::CloseAllStepEditors => {
+                if self.steps_edit.is_empty() {
+                    return false;
+                }
+                for i in self.steps_edit.drain(..) {
+                    self.steps[i].props.selected = false;
+                }
+                true
+            }
+            Msg::OpenAllStepEditors => {
+                let mut changed = false;
+                for (i, step) in self.steps.iter_mut().enumerate() {
+                    if self.steps_edit.insert(i) {
+                        step.props.selected = true;
+                        changed = true;
+                    }
+                }
+                changed
+            }
+            Msg::SetStepEnabled(index, value) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Modified step won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[index].props.enabled = value;
+                true
+            }
+            Msg::SetAllStepsEnabled(value) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Modified step won't affect the replacement that is already in progress."
+                    );
+                }
+                for step in self.steps.iter_mut() {
+                    step.props.enabled = value;
+                }
+                true
+            }
+            Msg::SetStepVirtualSort(index, virtual_sort) => {
+                self.steps[index].props.virtual_sort = virtual_sort;
+                true
+            }
+            Msg::SetStepRunOnlyIfPrevChanged(index, value) => {
+                self.steps[index].props.run_only_if_prev_changed = value;
+                true
+            }
+            Msg::SetStepPriority(index, priority) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Modified step won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[index].props.priority = priority;
+                true
+            }
+            Msg::SetStepPauseBetweenRegexes(index, pause) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Modified step won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[index].props.pause_between_regexes_ms = pause;
+                true
+            }
+            Msg::SetStepStopOnFirstMatch(index, value) => {
+                self.steps[index].props.stop_on_first_match = value;
+                true
+            }
+            Msg::SetStepInConfig(index, config, value) => {
+                let props = &mut self.steps[index].props;
+                match config {
+                    AbConfig::A => props.in_config_a = value,
+                    AbConfig::B => props.in_config_b = value,
+                }
+                true
+            }
+            Msg::UpdateStepTitle(step_index, title) => {
+                self.steps[step_index].props.title = title;
+                true
+            }
+            Msg::UpdateStepDescription(step_index, description) => {
+                self.steps[step_index].props.description = description;
+                true
+            }
+            Msg::AddRegex(step_index) => {
+                if !self.fits_more_regexes(ctx, step_index, 1) {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Added regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes.push(RegexInfo::default());
+                true
+            }
+            Msg::UpdateRegexTitle(step_index, regex_index, title) => {
+                self.steps[step_index].regexes[regex_index].title = title;
+                true
+            }
+            Msg::UpdateRegexSearch(step_index, regex_index, search) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes[regex_index].set_match(search);
+                self.update_match_counts();
+                // an invalid regex keeps the latest highlight while it's edited
+                if self.steps[step_index].regexes[regex_index].r#match.is_ok() {
+                    self.highlighted_regex = Some((step_index, regex_index));
+                    self.update_highlighted_ranges();
+                }
+                true
+            }
+            Msg::InsertCommonPattern(step_index, regex_index, key) => {
+                let (description, pattern) = match patterns::find(key) {
+                    Some(found) => found,
+                    None => {
+                        log::error!("There is no common pattern {}.", key);
+                        return false;
+                    }
+                };
+                self.steps[step_index].regexes[regex_index].title = description.into();
+                ctx.link().send_message(Msg::UpdateRegexSearch(
+                    step_index,
+                    regex_index,
+                    pattern.into(),
+                ));
+                true
+            }
+            Msg::UpdateGlobalRegexSearch(query) => {
+                self.regex_search_query = query;
+                true
+            }
+            Msg::UpdateNewGroupName(step_index, regex_index, name) => {
+                self.new_group_name = Some(((step_index, regex_index), name));
+                true
+            }
+            Msg::AddNamedGroup(step_index, regex_index, name) => {
+                let name = name.trim();
+                if !step::is_valid_group_name(name) {
+                    notify(
+                        ctx,
+                        Notification::warning(format!("The group name {:?} is not valid.", name)),
+                    );
+                    return false;
+                }
+                let regex = &mut self.steps[step_index].regexes[regex_index];
+                if regex.group_names().iter().any(|n| n == name) {
+                    notify(
+                        ctx,
+                        Notification::warning(format!(
+                            "The regex already has a group named {}.",
+                            name
+                        )),
+                    );
+                    return false;
+                }
+                regex.add_named_group(name);
+                self.new_group_name = None;
+                self.update_match_counts();
+                true
+            }
+            Msg::RenameRegexGroup(step_index, regex_index, old, new) => {
+                let new = new.trim();
+                if !step::is_valid_group_name(new) {
+                    notify(
+                        ctx,
+                        Notification::warning(format!("The group name {:?} is not valid.", new)),
+                    );
+                    return false;
+                }
+                let regex = &mut self.steps[step_index].regexes[regex_index];
+                if regex.group_names().iter().any(|n| n == new) {
+                    notify(
+                        ctx,
+                        Notification::warning(format!(
+                            "The regex already has a group named {}.",
+                            new
+                        )),
+                    );
+                    return false;
+                }
+                regex.rename_group(&old, new);
+                self.new_group_name = None;
+                self.update_match_counts();
+                true
+            }
+            Msg::UpdateRegexReplacement(step_index, regex_index, replacement) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes[regex_index].replace = replacement;
+                true
+            }
+            Msg::SetRegexSkipIfPrevNoMatch(step_index, regex_index, value) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes[regex_index].skip_if_prev_no_match = value;
+                true
+            }
+            Msg::SetRegexApplyOncePerLine(step_index, regex_index, value) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes[regex_index].apply_once_per_line = value;
+                true
+            }
+            Msg::SetRegexEnabled(step_index, regex_index, value) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes[regex_index].enabled = value;
+                true
+            }
+            Msg::SetAllRegexesEnabled(step_index, value) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regexes won't affect the replacement that is already in progress."
+                    );
+                }
+                for regex in self.steps[step_index].regexes.iter_mut() {
+                    regex.enabled = value;
+                }
+                true
+            }
+            Msg::UpdateRegexFlags(step_index, regex_index, flags) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes[regex_index].set_flags(flags);
+                self.update_match_counts();
+                self.update_highlighted_ranges();
+                true
+            }
+            Msg::DeleteRegex(step_index, regex_index) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Removed regex won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes.remove(regex_index);
+                self.remap_regex_positions(|i, j| match j {
+                    _ if i != step_index => Some((i, j)),
+                    j if j == regex_index => None,
+                    j if j > regex_index => Some((i, j - 1)),
+                    j => Some((i, j)),
+                });
+                true
+            }
+            Msg::MoveRegex(step_index, regex_index, direction) => {
+                if self.replacement_in_progress {
+                    log::warn!("Re-ordered regexes won't affect the replacement that is already in progress.");
+                }
+                let regexes = &mut self.steps[step_index].regexes;
+                let len = regexes.len();
+
+                let other = match direction {
+                    MoveDirection::Up if regex_index >= 1 => regex_index - 1,
+                    MoveDirection::Down if regex_index + 1 < len => regex_index + 1,
+                    _ => return false,
+                };
+                regexes.swap(regex_index, other);
+                self.remap_regex_positions(|i, j| match j {
+                    _ if i != step_index => Some((i, j)),
+                    j if j == regex_index => Some((i, other)),
+                    j if j == other => Some((i, regex_index)),
+                    j => Some((i, j)),
+                });
+                true
+            }
+            Msg::MoveRegexTo(step_index, from, to) => {
+                let regexes = &mut self.steps[step_index].regexes;
+                if from == to || from >= regexes.len() || to >= regexes.len() {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!("Re-ordered regexes won't affect the replacement that is already in progress.");
+                }
+                let regex = regexes.remove(from);
+                regexes.insert(to, regex);
+                self.remap_regex_positions(|i, j| {
+                    if i == step_index {
+                        Some((i, moved_index(j, from, to)))
+                    } else {
+                        Some((i, j))
+                    }
+                });
+                true
+            }
+            Msg::MoveRegexToStep(from_step, regex_index, to_step) => {
+                if from_step == to_step || !self.fits_more_regexes(ctx, to_step, 1) {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Moved regex won't affect the replacement that is already in progress."
+                    );
+                }
+                let regex = self.steps[from_step].regexes.remove(regex_index);
+                self.steps[to_step].regexes.push(regex);
+                let moved_to = self.steps[to_step].regexes.len() - 1;
+                self.remap_regex_positions(|i, j| match j {
+                    _ if i != from_step => Some((i, j)),
+                    j if j == regex_index => Some((to_step, moved_to)),
+                    j if j > regex_index => Some((i, j - 1)),
+                    j => Some((i, j)),
+                });
+                true
+            }
+            Msg::CopyRegexToStep(from_step, regex_index, to_step) => {
+                if !self.fits_more_regexes(ctx, to_step, 1) {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Copied regex won't affect the replacement that is already in progress."
+                    );
+                }
+                let regex = self.steps[from_step].regexes[regex_index].clone();
+                self.steps[to_step].regexes.push(regex);
+                true
+            }
+            Msg::DuplicateRegex(step_index, regex_index) => {
+                if !self.fits_more_regexes(ctx, step_index, 1) {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Duplicated regex won't affect the replacement that is already in progress."
+                    );
+                }
+                let regexes = &mut self.steps[step_index].regexes;
+                let regex = regexes[regex_index].clone();
+                regexes.insert(regex_index + 1, regex);
+                self.remap_regex_positions(|i, j| {
+                    if i == step_index && j > regex_index {
+                        Some((i, j + 1))
+                    } else {
+                        Some((i, j))
+                    }
+                });
+                true
+            }
+            Msg::MergeSteps(step_index) => {
+                if step_index + 1 >= self.steps.len() {
+                    log::error!("There is no next step to be merged with.");
+                    return false;
+                }
+                if !self.fits_more_regexes(
+                    ctx,
+                    step_index,
+                    self.steps[step_index + 1].regexes.len(),
+                ) {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Merged steps won't affect the replacement that is already in progress."
+                    );
+                }
+                let next = self.steps.remove(step_index + 1);
+                let step = &mut self.steps[step_index];
+                let merged_at = step.regexes.len();
+                step.props.title = [step.props.title.trim(), next.props.title.trim()]
+                    .into_iter()
+                    .filter(|t| !t.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" + ");
+                step.props.description =
+                    [step.props.description.trim(), next.props.description.trim()]
+                        .into_iter()
+                        .filter(|d| !d.is_empty())
+                        .collect::<Vec<_>>()
+                        .join("\n\n");
+                step.regexes.extend(next.regexes);
+
+                // the merged step stays open if any of the two were open
+                let was_open = self.steps_edit.contains(&step_index)
+                    || self.steps_edit.contains(&(step_index + 1));
+                self.remap_steps_edit(|i| match i {
+                    i if i <= step_index => Some(i),
+                    i if i == step_index + 1 => None,
+                    i => Some(i - 1),
+                });
+                if was_open {
+                    self.steps_edit.insert(step_index);
+                }
+                self.steps[step_index].props.selected = was_open;
+                self.remap_regex_positions(|i, j| match i {
+                    i if i <= step_index => Some((i, j)),
+                    i if i == step_index + 1 => Some((step_index, merged_at + j)),
+                    i => Some((i - 1, j)),
+                });
+                true
+            }
+            Msg::MoveStepTo(from, to) => {
+                if from == to || from >= self.steps.len() || to >= self.steps.len() {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Re-ordered steps won't affect the replacement that is already in progress."
+                    );
+                }
+                // the priorities stay in their positions, so the moved step
+                // runs at its new position
+                let priorities: Vec<usize> = self.steps.iter().map(|s| s.props.priority).collect();
+                let step = self.steps.remove(from);
+                self.steps.insert(to, step);
+                for (step, priority) in self.steps.iter_mut().zip(priorities) {
+                    step.props.priority = priority;
+                }
+
+                self.remap_steps_edit(|i| Some(moved_index(i, from, to)));
+                self.remap_regex_positions(|i, j| Some((moved_index(i, from, to), j)));
+                true
             }
-            Msg::UpdateRegexReplacement(step_index, regex_index, replacement) => {
+            Msg::SplitStep(step_index, regex_index) => {
+                if regex_index == 0 || regex_index >= self.steps[step_index].regexes.len() {
+                    log::error!("The step can only be split between two regexes.");
+                    return false;
+                }
                 if self.replacement_in_progress {
                     log::warn!(
-                        "Changed regex won't affect the replacement that is already in progress."
+                        "Split step won't affect the replacement that is already in progress."
                     );
                 }
-                self.steps[step_index].regexes[regex_index].replace = replacement;
+                let step = &mut self.steps[step_index];
+                let mut next = Step::default();
+                next.props.enabled = step.props.enabled;
+                // the split part runs right after, as it's declared next
+                next.props.priority = step.props.priority;
+                next.regexes = step.regexes.split_off(regex_index);
+                self.steps.insert(step_index + 1, next);
+
+                let was_open = self.steps_edit.contains(&step_index);
+                self.remap_steps_edit(|i| {
+                    if i <= step_index {
+                        Some(i)
+                    } else {
+                        Some(i + 1)
+                    }
+                });
+                if was_open {
+                    self.steps_edit.insert(step_index + 1);
+                    self.steps[step_index + 1].props.selected = true;
+                }
+                self.remap_regex_positions(|i, j| match i {
+                    i if i < step_index => Some((i, j)),
+                    i if i == step_index && j < regex_index => Some((i, j)),
+                    i if i == step_index => Some((i + 1, j - regex_index)),
+                    i => Some((i + 1, j)),
+                });
                 true
             }
-            Msg::DeleteRegex(step_index, regex_index, confirmed) => {
+            Msg::ImportRegexesFromText(step_index, text) => {
                 if self.replacement_in_progress {
                     log::warn!(
-                        "Removed regex won't affect the replacement that is already in progress."
+                        "Imported regexes won't affect the replacement that is already in progress."
                     );
                 }
-                if confirmed {
-                    self.steps[step_index].regexes.remove(regex_index);
-                    true
+                let regexes = step::regexes_from_text(&text);
+                if regexes.is_empty() {
+                    notify(
+                        ctx,
+                        Notification::warning("No regexes were found in the imported text."),
+                    );
+                    return false;
+                }
+                if !self.fits_more_regexes(ctx, step_index, regexes.len()) {
+                    return false;
+                }
+                self.steps[step_index].regexes.extend(regexes);
+                self.update_match_counts();
+                true
+            }
+            Msg::PasteRegexesFromClipboard(step_index) => {
+                ctx.link().send_future(async move {
+                    Msg::ClipboardRegexesRead(step_index, read_clipboard_text().await)
+                });
+                false
+            }
+            Msg::PasteRegexFromJsonClipboard(step_index) => {
+                ctx.link().send_future(async move {
+                    Msg::ClipboardJsonRegexesRead(step_index, read_clipboard_text().await)
+                });
+                false
+            }
+            Msg::ClipboardJsonRegexesRead(step_index, text) => {
+                let regexes = match text.and_then(|text| step::regexes_from_json(&text)) {
+                    Ok(regexes) => regexes,
+                    Err(err) => {
+                        notify(
+                            ctx,
+                            Notification::error(format!(
+                                "Failed to read regexes from the clipboard's JSON: {}",
+                                err
+                            )),
+                        );
+                        return false;
+                    }
+                };
+                if regexes.is_empty() {
+                    notify(
+                        ctx,
+                        Notification::warning("No regexes were found in the clipboard."),
+                    );
+                    return false;
+                }
+                if !self.fits_more_regexes(ctx, step_index, regexes.len()) {
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Pasted regexes won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps[step_index].regexes.extend(regexes);
+                self.update_match_counts();
+                true
+            }
+            Msg::ClipboardRegexesRead(step_index, text) => {
+                let text = match text {
+                    Ok(text) => text,
+                    Err(err) => {
+                        notify(
+                            ctx,
+                            Notification::error(format!("Failed to read the clipboard: {}", err)),
+                        );
+                        return false;
+                    }
+                };
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Pasted regexes won't affect the replacement that is already in progress."
+                    );
+                }
+                let regexes = step::regexes_from_lines(&text);
+                if regexes.is_empty() {
+                    notify(
+                        ctx,
+                        Notification::warning("No regexes were found in the clipboard."),
+                    );
+                    return false;
+                }
+                if !self.fits_more_regexes(ctx, step_index, regexes.len()) {
+                    return false;
+                }
+                self.steps[step_index].regexes.extend(regexes);
+                self.update_match_counts();
+                true
+            }
+            Msg::LoadTemplate(template_index) => {
+                let template = &templates::TEMPLATES[template_index];
+                let steps = template.steps();
+                if let Some(max) = self.settings.max_regexes_per_step {
+                    if let Some(i) = steps.iter().position(|step| step.regexes.len() > max) {
+                        notify(
+                            ctx,
+                            Notification::warning(format!(
+                                "Step {} of the template has more than {} regexes.",
+                                i + 1,
+                                max
+                            )),
+                        );
+                        return false;
+                    }
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Loaded template won't affect the replacement that is already in progress."
+                    );
+                }
+                self.steps = steps;
+                self.remap_regex_positions(|_, _| None);
+                self.steps_edit.clear();
+                self.steps_edit.insert(0);
+                self.steps[0].props.selected = true;
+                self.update_match_counts();
+                true
+            }
+            Msg::ExportAsMarkdown => {
+                let md = export::steps_to_markdown(&self.steps);
+                export::download_text_file("replacer-steps.md", "text/markdown", &md);
+                false
+            }
+            Msg::ExportStepAsText(step_index) => {
+                let step = &self.steps[step_index];
+                let title = if step.props.title.trim().is_empty() {
+                    "New Step"
                 } else {
-                    true
+                    &step.props.title
+                };
+                let filename = format!(
+                    "step-{}-{}.txt",
+                    step_index + 1,
+                    export::file_name_safe(title)
+                );
+                export::download_text_file(&filename, "text/plain", &export::step_to_text(step));
+                false
+            }
+            Msg::ExportStepAsToolJson(step_index) => {
+                let filename = format!("step-{}.regex.json", step_index + 1);
+                let json = export::step_to_tool_json(&self.steps[step_index]);
+                export::download_text_file(&filename, "application/json", &json);
+                false
+            }
+            Msg::AddTestCase(step_index, regex_index) => {
+                self.steps[step_index].regexes[regex_index]
+                    .test_cases
+                    .push(RegexTestCase::default());
+                true
+            }
+            Msg::UpdateTestCase(step_index, regex_index, case_index, field, value) => {
+                let case = &mut self.steps[step_index].regexes[regex_index].test_cases[case_index];
+                match field {
+                    TestCaseField::Input => case.input = value,
+                    TestCaseField::ExpectedOutput => case.expected_output = value,
+                }
+                case.passed = None;
+                true
+            }
+            Msg::RunTestCases(step_index, regex_index) => {
+                self.steps[step_index].regexes[regex_index].run_test_cases();
+                true
+            }
+            Msg::TestAllRegexesInStep(step_index) => {
+                for regex in self.steps[step_index]
+                    .regexes
+                    .iter_mut()
+                    .filter(|r| r.enabled && !r.test_cases.is_empty())
+                {
+                    regex.run_test_cases();
                 }
+                true
             }
-            Msg::MoveRegex(step_index, regex_index, direction) => {
+            Msg::ShowRegexDiagram(step_index, regex_index) => {
+                self.regex_diagram = Some((step_index, regex_index));
+                true
+            }
+            Msg::CloseRegexDiagram => {
+                self.regex_diagram = None;
+                true
+            }
+            Msg::BenchmarkRegex(step_index, regex_index) => {
+                let input = match self.active_text_project {
+                    Some(i) => &self.text_projects[i].input,
+                    None => {
+                        notify(
+                            ctx,
+                            Notification::warning(
+                                "A text project must be active to benchmark a regex.",
+                            ),
+                        );
+                        return false;
+                    }
+                };
+                self.steps[step_index].regexes[regex_index].benchmark(input);
+                true
+            }
+            Msg::AnalyzeRegexComplexity(step_index, regex_index) => {
+                self.steps[step_index].regexes[regex_index].analyze_complexity();
+                true
+            }
+            Msg::ResetToDefaults => {
                 if self.replacement_in_progress {
-                    log::warn!("Re-ordered regexes won't affect the replacement that is already in progress.");
+                    notify(
+                        ctx,
+                        Notification::error("Cannot reset while a replacement is in progress."),
+                    );
+                    return false;
                 }
-                let regexes = &mut self.steps[step_index].regexes;
-                let len = regexes.len();
-
-                match direction {
-                    MoveDirection::Up => {
-                        if regex_index >= 1 {
-                            regexes.swap(regex_index - 1, regex_index);
-                            true
-                        } else {
-                            false
+                // the settings, global variables and run history are kept
+                self.text_projects = vec![TextProject::default()];
+                self.active_text_project = Some(0);
+                self.output_search = None;
+                self.tag_filter = None;
+                self.new_variable = NewVariable::default();
+                let mut step = Step::default();
+                step.props.selected = true;
+                self.steps = vec![step];
+                self.steps_edit.clear();
+                self.steps_edit.insert(0);
+                self.remap_regex_positions(|_, _| None);
+                self.update_match_counts();
+                true
+            }
+            Msg::OpenSettings => {
+                self.settings_open = true;
+                self.settings_draft = SettingsDraft::new(&self.settings);
+                true
+            }
+            Msg::CloseSettings => {
+                ctx.link().send_message(Msg::ApplySettingsDraft);
+                self.settings_open = false;
+                true
+            }
+            Msg::ToggleDarkMode => {
+                let mut settings = self.settings.clone();
+                settings.theme = settings.theme.toggled();
+                ctx.link().send_message(Msg::UpdateSettings(settings));
+                false
+            }
+            Msg::UpdateSettings(settings) => {
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed settings won't affect the replacement that is already in progress."
+                    );
+                }
+                settings.save();
+                self.settings = settings;
+                true
+            }
+            Msg::UpdateSettingsDraft(draft) => {
+                self.settings_draft = draft;
+                true
+            }
+            Msg::ApplySettingsDraft => {
+                match self.settings_draft.apply(&self.settings) {
+                    Ok(settings) => {
+                        self.settings_draft = SettingsDraft::new(&settings);
+                        if settings != self.settings {
+                            ctx.link().send_message(Msg::UpdateSettings(settings));
                         }
                     }
-                    MoveDirection::Down => {
-                        if regex_index + 1 < len {
-                            regexes.swap(regex_index, regex_index + 1);
-                            true
-                        } else {
-                            false
-                        }
+                    Err(err) => {
+                        notify(ctx, Notification::warning(err));
+                        self.settings_draft = SettingsDraft::new(&self.settings);
                     }
                 }
+                true
             }
-            Msg::InputUpdated(project_index, value) => {
+            Msg::ShowStats(open) => {
+                self.stats_open = open;
+                true
+            }
+            Msg::ShowVariables(open) => {
+                self.variables_open = open;
+                true
+            }
+            Msg::SetVariable(name, value) => {
+                if !variables::is_valid_name(&name) {
+                    notify(
+                        ctx,
+                        Notification::error(format!("Invalid variable name: {:?}", name)),
+                    );
+                    return false;
+                }
+                if self.replacement_in_progress {
+                    log::warn!(
+                        "Changed variable won't affect the replacement that is already in progress."
+                    );
+                }
+                self.variables.insert(name, value);
+                true
+            }
+            Msg::DeleteVariable(name) => {
+                self.variables.shift_remove(&name);
+                true
+            }
+            Msg::UpdateNewVariableName(project_index, name) => {
+                self.new_variable.write_on(project_index).name = name;
+                true
+            }
+            Msg::UpdateNewVariableValue(project_index, value) => {
+                self.new_variable.write_on(project_index).value = value;
+                true
+            }
+            Msg::AddVariable => {
+                let name = self.new_variable.write_on(None).name.trim().to_string();
+                if !variables::is_valid_name(&name) {
+                    notify(
+                        ctx,
+                        Notification::error(format!("Invalid variable name: {:?}", name)),
+                    );
+                    return false;
+                }
+                let value = std::mem::take(&mut self.new_variable).value;
+                self.variables.insert(name, value);
+                true
+            }
+            Msg::ShowProjectVariables(open) => {
+                self.project_variables_open = open;
+                true
+            }
+            Msg::SetProjectVariable(project_index, name, value) => {
+                if !variables::is_valid_name(&name) {
+                    notify(
+                        ctx,
+                        Notification::error(format!("Invalid variable name: {:?}", name)),
+                    );
+                    return false;
+                }
                 if self.replacement_in_progress {
-                    log::error!("A replacement is already in progress.");
+                    log::warn!(
+                        "Changed variable won't affect the replacement that is already in progress."
+                    );
+                }
+                self.text_projects[project_index]
+                    .local_variables
+                    .insert(name, value);
+                true
+            }
+            Msg::DeleteProjectVariable(project_index, name) => {
+                self.text_projects[project_index]
+                    .local_variables
+                    .shift_remove(&name);
+                true
+            }
+            Msg::AddProjectVariable(project_index) => {
+                let name = self
+                    .new_variable
+                    .write_on(Some(project_index))
+                    .name
+                    .trim()
+                    .to_string();
+                if !variables::is_valid_name(&name) {
+                    notify(
+                        ctx,
+                        Notification::error(format!("Invalid variable name: {:?}", name)),
+                    );
+                    return false;
+                }
+                let value = std::mem::take(&mut self.new_variable).value;
+                self.text_projects[project_index]
+                    .local_variables
+                    .insert(name, value);
+                true
+            }
+            Msg::RequestConfirmation(action) => {
+                self.pending_confirmation = Some(action);
+                true
+            }
+            Msg::ConfirmPendingAction => match self.pending_confirmation.take() {
+                Some(action) => {
+                    ctx.link().send_message(action.into_msg());
+                    true
+                }
+                None => false,
+            },
+            Msg::CancelPendingAction => {
+                self.pending_confirmation = None;
+                true
+            }
+            Msg::AddNotification(notification) => {
+                notification.log();
+                self.notifications.push_back(notification);
+                ctx.link().send_future(async {
+                    gloo_timers::future::TimeoutFuture::new(notification::NOTIFICATION_DURATION_MS)
+                        .await;
+                    Msg::DismissExpiredNotifications
+                });
+                true
+            }
+            Msg::DismissNotification(index) => self.notifications.remove(index).is_some(),
+            Msg::DismissExpiredNotifications => {
+                let now = js_sys::Date::now();
+                let len = self.notifications.len();
+                self.notifications.retain(|n| n.expires_at > now);
+                self.notifications.len() != len
+            }
+            Msg::HighlightRegexMatches(regex) => {
+                if regex.is_some() {
+                    self.last_focused_regex = regex;
+                }
+                self.highlighted_regex = regex;
+                self.update_highlighted_ranges();
+                true
+            }
+            Msg::DismissWelcome => {
+                let mut settings = self.settings.clone();
+                settings.welcome_dismissed = true;
+                ctx.link().send_message(Msg::UpdateSettings(settings));
+                false
+            }
+            Msg::ToggleCheatsheet => {
+                self.cheatsheet_open = !self.cheatsheet_open;
+                true
+            }
+            Msg::SetStepsDropdownExpanded(expanded) => {
+                if self.steps_dropdown_expanded == expanded {
                     return false;
                 }
+                self.steps_dropdown_expanded = expanded;
+                true
+            }
+            Msg::InsertIntoRegex(construct) => {
+                let regex = self
+                    .last_focused_regex
+                    .and_then(|(i, j)| self.steps.get_mut(i)?.regexes.get_mut(j));
+                let regex = match regex {
+                    Some(regex) => regex,
+                    None => {
+                        notify(
+                            ctx,
+                            Notification::warning("Focus a regex match field to insert into it."),
+                        );
+                        return false;
+                    }
+                };
+                let search = match &regex.r#match {
+                    Ok(re) => format!("{}{}", re.as_str(), construct),
+                    Err(search) => format!("{}{}", search, construct),
+                };
+                regex.set_match(search);
+                self.update_match_counts();
+                true
+            }
+            Msg::InputUpdated(project_index, value) => {
                 let project = &mut self.text_projects[project_index];
                 project.input = value;
-                project.output_status = OutputStatus::Outdated;
+                if !self.replacement_in_progress {
+                    project.output_status = OutputStatus::Outdated;
+                }
+                let char_count = project.input.chars().count();
+                project.record(ProjectEvent::InputChanged {
+                    at: js_sys::Date::now(),
+                    char_count,
+                });
                 // project.output = value;
+                self.update_match_counts();
+                self.update_highlighted_ranges();
+                if self.replacement_in_progress {
+                    self.input_edited_during_run = true;
+                    if self.settings.auto_run {
+                        // the stale run is restarted over the new input
+                        // once it's cancelled
+                        self.auto_run_restart = Some(project_index);
+                        self.replacement_cancel_signal.store(true, Ordering::SeqCst);
+                    }
+                } else if self.settings.auto_run {
+                    ctx.link()
+                        .send_message(Msg::StartReplacingText(Some(project_index)));
+                }
                 true
             }
             Msg::OutputUpdated(_project_index, _discarded_value) => {
                 log::error!("This should never be triggered.");
                 false
             }
+            Msg::ToggleOutputSearch => {
+                self.output_search = match self.output_search {
+                    Some(_) => None,
+                    None => Some(OutputSearch::default()),
+                };
+                true
+            }
+            Msg::UpdateOutputSearch(query) => {
+                if let Some(search) = &mut self.output_search {
+                    search.query = query;
+                    search.current = 0;
+                }
+                true
+            }
+            Msg::SetOutputSearchCaseInsensitive(value) => {
+                if let Some(search) = &mut self.output_search {
+                    search.case_insensitive = value;
+                    search.current = 0;
+                }
+                true
+            }
+            Msg::MoveOutputSearch(direction) => {
+                let output = match self.active_text_project {
+                    Some(i) => &self.text_projects[i].output,
+                    None => return false,
+                };
+                let search = match &mut self.output_search {
+                    Some(search) => search,
+                    None => return false,
+                };
+                let total = search.find_ranges(output).len();
+                if total == 0 {
+                    return false;
+                }
+                search.current = match direction {
+                    MoveDirection::Up => (search.current + total - 1) % total,
+                    MoveDirection::Down => (search.current + 1) % total,
+                };
+                self.scroll_to_output_search = true;
+                true
+            }
+            Msg::ShowInputToolbar(open) => {
+                self.input_toolbar_open = open;
+                true
+            }
+            Msg::ToggleInputCollapsed => {
+                self.input_collapsed = !self.input_collapsed;
+                true
+            }
+            Msg::SyncScroll(id, scroll_top) => {
+                let current = match id {
+                    TextAreaId::Input => &mut self.input_scroll_top,
+                    TextAreaId::Output => &mut self.output_scroll_top,
+                };
+                let changed = *current != scroll_top;
+                *current = scroll_top;
+                changed
+            }
+            Msg::ToggleInputWrap(project_index) => {
+                let project = &mut self.text_projects[project_index];
+                project.wrap_input = !project.wrap_input;
+                true
+            }
+            Msg::ToggleOutputWrap(project_index) => {
+                let project = &mut self.text_projects[project_index];
+                project.wrap_output = !project.wrap_output;
+                true
+            }
+            Msg::GeneratePrintView(project_index) => {
+                let html =
+                    export::project_to_print_html(&self.text_projects[project_index], &self.steps);
+                export::open_html_in_new_tab(&html);
+                false
+            }
+            Msg::ExportProjectAsHtml(project_index) => {
+                let project = &self.text_projects[project_index];
+                let html = export::project_to_print_html(project, &self.steps);
+                let filename = format!("{}.html", export::file_name_safe(&project.props.title));
+                export::download_text_file(&filename, "text/html", &html);
+                false
+            }
+            Msg::ToggleRtlMode(project_index) => {
+                let project = &mut self.text_projects[project_index];
+                project.rtl_mode = !project.rtl_mode;
+                true
+            }
+            Msg::UpdateInputUrl(project_index, url) => {
+                self.text_projects[project_index].input_url = url;
+                true
+            }
+            Msg::LoadInputFromUrl(project_index, url) => {
+                if self.replacement_in_progress {
+                    notify(
+                        ctx,
+                        Notification::error("A replacement is already in progress."),
+                    );
+                    return false;
+                }
+                self.text_projects[project_index].input_fetch_status =
+                    Some(InputFetchStatus::Loading);
+                ctx.link().send_future(async move {
+                    let response = gloo_net::http::Request::get(&url).send().await;
+                    let result = match response {
+                        Ok(response) if response.ok() => {
+                            response.text().await.map_err(|err| err.to_string())
+                        }
+                        Ok(response) => {
+                            Err(format!("{} {}", response.status(), response.status_text()))
+                        }
+                        Err(err) => Err(err.to_string()),
+                    };
+                    Msg::InputFetched(project_index, result)
+                });
+                true
+            }
+            Msg::InputFetched(project_index, result) => {
+                // the project may have been removed while the input loaded
+                let project = match self.text_projects.get_mut(project_index) {
+                    Some(project) => project,
+                    None => return false,
+                };
+                match result {
+                    Ok(text) => {
+                        project.input_fetch_status = None;
+                        ctx.link()
+                            .send_message(Msg::InputUpdated(project_index, text));
+                    }
+                    Err(err) => {
+                        log::error!("Failed to load the input from the URL: {}", err);
+                        project.input_fetch_status = Some(InputFetchStatus::Failed(err));
+                    }
+                }
+                true
+            }
+            Msg::FindInInput(find) => {
+                self.input_find = find;
+                true
+            }
+            Msg::UpdateInputReplaceText(replace) => {
+                self.input_replace = replace;
+                true
+            }
+            Msg::ReplaceInInput(find, replace) => self.replace_in_input(&find, &replace, false),
+            Msg::ReplaceAllInInput(find, replace) => self.replace_in_input(&find, &replace, true),
             Msg::AddTextProject => {
                 let next_text_project = TextProject::default();
                 self.text_projects.push(next_text_project);
                 self.active_text_project = Some(self.text_projects.len() - 1);
+                self.update_match_counts();
+                self.update_highlighted_ranges();
+                true
+            }
+            Msg::UpdateSplitDelimiter(delimiter) => {
+                self.split_delimiter = delimiter;
+                true
+            }
+            Msg::SplitClipboardIntoProjects(delimiter) => {
+                ctx.link().send_future(async move {
+                    Msg::ClipboardProjectsRead(delimiter, read_clipboard_text().await)
+                });
+                false
+            }
+            Msg::ClipboardProjectsRead(delimiter, text) => {
+                let text = match text {
+                    Ok(text) => text,
+                    Err(err) => {
+                        notify(
+                            ctx,
+                            Notification::error(format!("Failed to read the clipboard: {}", err)),
+                        );
+                        return false;
+                    }
+                };
+                let delimiter = delimiter.replace(r"\n", "\n").replace(r"\t", "\t");
+                if delimiter.is_empty() {
+                    notify(ctx, Notification::warning("The delimiter is empty."));
+                    return false;
+                }
+                let segments: Vec<&str> = text
+                    .split(delimiter.as_str())
+                    .filter(|segment| !segment.trim().is_empty())
+                    .collect();
+                if segments.is_empty() {
+                    notify(
+                        ctx,
+                        Notification::warning("No texts were found in the clipboard."),
+                    );
+                    return false;
+                }
+                notify(
+                    ctx,
+                    Notification::info(format!(
+                        "{} projects were created from the clipboard.",
+                        segments.len()
+                    )),
+                );
+                for (n, segment) in segments.into_iter().enumerate() {
+                    let mut project = TextProject::default();
+                    project.props.title = format!("Project {}", n + 1);
+                    project.input = segment.to_string();
+                    project.output_status = OutputStatus::Outdated;
+                    self.text_projects.push(project);
+                }
+                self.active_text_project = Some(self.text_projects.len() - 1);
+                self.update_match_counts();
+                self.update_highlighted_ranges();
+                true
+            }
+            Msg::CloneProject(index) => {
+                if self.replacement_in_progress {
+                    notify(
+                        ctx,
+                        Notification::error("A replacement is already in progress."),
+                    );
+                    return false;
+                }
+                let original = &self.text_projects[index];
+                let title = if original.props.title.trim().is_empty() {
+                    "New Project"
+                } else {
+                    &original.props.title
+                };
+                let mut clone = TextProject::default();
+                clone.props.title = format!("{} (processed)", title);
+                clone.props.commentary = original.props.commentary.clone();
+                clone.props.tags = original.props.tags.clone();
+                clone.input = original.input.clone();
+                clone.local_variables = original.local_variables.clone();
+                self.text_projects.push(clone);
+                let clone_index = self.text_projects.len() - 1;
+                self.active_text_project = Some(clone_index);
+                self.update_match_counts();
+                self.update_highlighted_ranges();
+                // the original keeps the raw text, to compare against
+                ctx.link()
+                    .send_message(Msg::StartReplacingText(Some(clone_index)));
                 true
             }
             Msg::SelectTextProject(index) => {
@@ -316,9 +3265,98 @@ impl Component for Model {
                     false
                 } else {
                     self.active_text_project = Some(index);
+                    self.update_match_counts();
+                    self.update_highlighted_ranges();
                     true
                 }
             }
+            Msg::UpdateWebhookUrl(index, url) => {
+                let url = url.trim();
+                self.text_projects[index].props.webhook_url =
+                    (!url.is_empty()).then(|| url.to_string());
+                true
+            }
+            Msg::ArchiveOutput(index) => {
+                let snapshot = self.step_snapshot();
+                self.text_projects[index].archive_output(snapshot);
+                true
+            }
+            Msg::UpdateNewTag(tag) => {
+                self.new_tag = tag;
+                true
+            }
+            Msg::AddProjectTag(index, tag) => {
+                let tag = tag.trim();
+                if tag.is_empty() {
+                    return false;
+                }
+                let tags = &mut self.text_projects[index].props.tags;
+                if !tags.iter().any(|t| t == tag) {
+                    tags.push(tag.to_string());
+                }
+                self.new_tag.clear();
+                true
+            }
+            Msg::RemoveProjectTag(index, tag) => {
+                self.text_projects[index].props.tags.retain(|t| *t != tag);
+                // a filter by a tag that no project has would hide every tab
+                if self.tag_filter.as_ref() == Some(&tag)
+                    && !self
+                        .text_projects
+                        .iter()
+                        .any(|p| p.props.tags.contains(&tag))
+                {
+                    self.tag_filter = None;
+                }
+                true
+            }
+            Msg::FilterTabsByTag(tag) => {
+                self.tag_filter = tag;
+                true
+            }
+            Msg::SendOutputToWebhook(project_index) => {
+                let project = &mut self.text_projects[project_index];
+                let url = match &project.props.webhook_url {
+                    Some(url) => url.clone(),
+                    None => {
+                        log::error!("The project has no webhook URL");
+                        return false;
+                    }
+                };
+                let content = project.output.clone();
+                project.webhook_status = Some(WebhookStatus::Sending);
+                ctx.link().send_future(async move {
+                    let response = gloo_net::http::Request::post(&url)
+                        .header("Content-Type", "text/plain; charset=utf-8")
+                        .body(content)
+                        .send()
+                        .await;
+                    let result = match response {
+                        Ok(response) if response.ok() => Ok(response.status()),
+                        Ok(response) => {
+                            Err(format!("{} {}", response.status(), response.status_text()))
+                        }
+                        Err(err) => Err(err.to_string()),
+                    };
+                    Msg::WebhookResponded(project_index, result)
+                });
+                true
+            }
+            Msg::WebhookResponded(project_index, result) => {
+                let status = match result {
+                    Ok(code) => WebhookStatus::Sent(code),
+                    Err(err) => {
+                        log::error!("Failed to send the output to the webhook: {}", err);
+                        WebhookStatus::Failed(err)
+                    }
+                };
+                // the project may have been removed while the output was sent
+                match self.text_projects.get_mut(project_index) {
+                    Some(project) => project.webhook_status = Some(status),
+                    None => return false,
+                }
+                true
+            }
             Msg::UpdateTextProjectTitle(index, title) => {
                 let project = &mut self.text_projects[index];
                 project.props.title = title;
@@ -327,50 +3365,78 @@ impl Component for Model {
             Msg::StartReplacingText(project_index) => {
                 if let Some(project_index) = project_index {
                     if self.replacement_in_progress {
-                        log::error!("Replacement already in progress");
+                        notify(ctx, Notification::error("Replacement already in progress"));
                         return false;
                     }
 
-                    self.replacement_in_progress = true;
-                    let project = &mut self.text_projects[project_index];
-                    project.output_status = OutputStatus::InProgress;
-
-                    let mut regexes = vec![];
-
-                    for (i, step) in self.steps.iter().enumerate() {
-                        let mut regexes_i = vec![];
+                    let regexes = match self.collect_steps_regexes(project_index) {
+                        Some(regexes) => regexes,
+                        None => return true,
+                    };
 
-                        for re in step.regexes.iter() {
-                            let r#match = match &re.r#match {
-                                Ok(r) => r,
-                                Err(s) if s.is_empty() => {
-                                    continue;
-                                }
-                                Err(s) => {
-                                    log::error!("The regex {} had a parse error", s);
-                                    return true;
-                                }
-                            };
-                            let repl = &re.replace;
-                            regexes_i.push((r#match.clone(), repl.clone()));
-                        }
-                        regexes.push(regexes_i);
+                    // the finished output would otherwise be lost
+                    if matches!(
+                        self.text_projects[project_index].output_status,
+                        OutputStatus::Done
+                    ) {
+                        let snapshot = self.step_snapshot();
+                        self.text_projects[project_index].archive_output(snapshot);
                     }
 
-                    let mut content = project.input.clone();
+                    self.replacement_in_progress = true;
+                    self.replacement_eta = None;
+                    self.current_run = Some(RunInProgress::new(&regexes));
+                    let project = &mut self.text_projects[project_index];
+                    project.record(ProjectEvent::ReplacementStarted {
+                        at: js_sys::Date::now(),
+                    });
+                    self.screen_reader_message = "Replacement started".to_string();
+                    project.output_status = OutputStatus::InProgress {
+                        processed: 0,
+                        total: 1,
+                        completed_steps: 0,
+                        total_steps: regexes.len(),
+                        fired_regex: None,
+                    };
+
+                    let content = project.input.clone();
 
                     self.replacement_cancel_signal
                         .store(false, Ordering::SeqCst);
+                    self.pause_signal.store(false, Ordering::SeqCst);
                     let cancel_signal = self.replacement_cancel_signal.clone();
+                    let pause_signal = self.pause_signal.clone();
+                    let progress = ctx.link().callback(move |(completed, total, fired)| {
+                        Msg::UpdateProgress(project_index, completed, total, fired)
+                    });
+                    let callbacks = ReplacementCallbacks {
+                        progress,
+                        eta: ctx.link().callback(Msg::UpdateEta),
+                        paused: ctx
+                            .link()
+                            .callback(move |content| Msg::ShowPausedOutput(project_index, content)),
+                    };
+                    let log_sender = self.start_execution_log(ctx);
+                    let limits = self.replacement_limits();
                     ctx.link().send_future(async move {
-                        content = match replace_text(content, regexes, cancel_signal).await {
-                            Ok(content) => content,
+                        let result = match replace_text(
+                            content,
+                            regexes,
+                            cancel_signal,
+                            pause_signal,
+                            limits,
+                            callbacks,
+                            log_sender,
+                        )
+                        .await
+                        {
+                            Ok(result) => result,
                             Err((motive, content)) => {
                                 return Msg::CancelledReplacingText(project_index, motive, content);
                             }
                         };
 
-                        Msg::FinishReplacingText(project_index, content)
+                        Msg::FinishReplacingText(project_index, result)
                     });
 
                     true
@@ -378,6 +3444,277 @@ impl Component for Model {
                     false
                 }
             }
+            Msg::StartStreamingReplacement(project_index, chunk_size) => {
+                if self.replacement_in_progress {
+                    notify(ctx, Notification::error("Replacement already in progress"));
+                    return false;
+                }
+
+                let regexes = match self.collect_steps_regexes(project_index) {
+                    Some(regexes) => regexes,
+                    None => return true,
+                };
+
+                // the finished output would otherwise be lost
+                if matches!(
+                    self.text_projects[project_index].output_status,
+                    OutputStatus::Done
+                ) {
+                    let snapshot = self.step_snapshot();
+                    self.text_projects[project_index].archive_output(snapshot);
+                }
+
+                self.replacement_in_progress = true;
+                self.current_run = Some(RunInProgress::new(&regexes));
+                let project = &mut self.text_projects[project_index];
+                project.record(ProjectEvent::ReplacementStarted {
+                    at: js_sys::Date::now(),
+                });
+                self.screen_reader_message = "Replacement started".to_string();
+                let chunks = split_into_chunks(&project.input, chunk_size);
+                let total = chunks.len();
+                project.output.clear();
+                project.output_status = OutputStatus::InProgress {
+                    processed: 0,
+                    total,
+                    completed_steps: 0,
+                    total_steps: regexes.len(),
+                    fired_regex: None,
+                };
+
+                self.replacement_cancel_signal
+                    .store(false, Ordering::SeqCst);
+                self.pause_signal.store(false, Ordering::SeqCst);
+                let cancel_signal = self.replacement_cancel_signal.clone();
+                let pause_signal = self.pause_signal.clone();
+                // the estimate and the paused text would only cover the
+                // current chunk
+                let callbacks = ReplacementCallbacks {
+                    progress: ctx.link().callback(move |(completed, total, fired)| {
+                        Msg::UpdateProgress(project_index, completed, total, fired)
+                    }),
+                    ..ReplacementCallbacks::noop()
+                };
+                let log_sender = self.start_execution_log(ctx);
+                let limits = self.replacement_limits();
+                let link = ctx.link().clone();
+                ctx.link().send_future(async move {
+                    let mut result = ReplacementResult {
+                        content: String::new(),
+                        regex_durations: vec![],
+                        regex_matches: vec![],
+                    };
+                    for (i, chunk) in chunks.into_iter().enumerate() {
+                        // cycle detection and growth limits apply per-chunk
+                        match replace_text(
+                            chunk,
+                            regexes.clone(),
+                            cancel_signal.clone(),
+                            pause_signal.clone(),
+                            limits,
+                            callbacks.clone(),
+                            log_sender.clone(),
+                        )
+                        .await
+                        {
+                            Ok(replaced) => {
+                                link.send_message(Msg::AppendStreamedChunk(
+                                    project_index,
+                                    replaced.content.clone(),
+                                    i + 1,
+                                    total,
+                                ));
+                                result.append(replaced);
+                            }
+                            Err((motive, replaced)) => {
+                                result.content.push_str(&replaced);
+                                return Msg::CancelledReplacingText(
+                                    project_index,
+                                    motive,
+                                    result.content,
+                                );
+                            }
+                        }
+                    }
+
+                    Msg::FinishReplacingText(project_index, result)
+                });
+
+                true
+            }
+            Msg::StartABComparison(project_index) => {
+                if self.replacement_in_progress {
+                    notify(ctx, Notification::error("Replacement already in progress"));
+                    return false;
+                }
+
+                let regexes = match self.collect_steps_regexes(project_index) {
+                    Some(regexes) => regexes,
+                    None => return true,
+                };
+                let (regexes_a, regexes_b): (Vec<_>, Vec<_>) = (
+                    regexes
+                        .iter()
+                        .filter(|(props, _)| props.in_config_a)
+                        .cloned()
+                        .collect(),
+                    regexes
+                        .into_iter()
+                        .filter(|(props, _)| props.in_config_b)
+                        .collect(),
+                );
+
+                self.replacement_in_progress = true;
+                self.replacement_cancel_signal
+                    .store(false, Ordering::SeqCst);
+                self.pause_signal.store(false, Ordering::SeqCst);
+                let cancel_signal = self.replacement_cancel_signal.clone();
+                let pause_signal = self.pause_signal.clone();
+                let content = self.text_projects[project_index].input.clone();
+                let limits = self.replacement_limits();
+                self.ab_progress = Some([(0, 0); 2]);
+                let callbacks = |config: AbConfig| ReplacementCallbacks {
+                    progress: ctx.link().callback(move |(completed, total, _)| {
+                        Msg::UpdateABProgress(config, completed, total)
+                    }),
+                    ..ReplacementCallbacks::noop()
+                };
+                let (callbacks_a, callbacks_b) = (callbacks(AbConfig::A), callbacks(AbConfig::B));
+                ctx.link().send_future(async move {
+                    // the comparison has no execution log
+                    let (log_sender, _) = futures::channel::mpsc::channel(0);
+                    let (a, b) = futures::future::join(
+                        replace_text(
+                            content.clone(),
+                            regexes_a,
+                            cancel_signal.clone(),
+                            pause_signal.clone(),
+                            limits,
+                            callbacks_a,
+                            log_sender.clone(),
+                        ),
+                        replace_text(
+                            content,
+                            regexes_b,
+                            cancel_signal,
+                            pause_signal,
+                            limits,
+                            callbacks_b,
+                            log_sender,
+                        ),
+                    )
+                    .await;
+                    let content =
+                        |result: Result<ReplacementResult, (CancelMotive, String)>| match result {
+                            Ok(result) => (result.content, None),
+                            Err((motive, content)) => (content, Some(motive)),
+                        };
+                    Msg::FinishABComparison(project_index, content(a), content(b))
+                });
+                true
+            }
+            Msg::FinishABComparison(project_index, (a, motive_a), (b, motive_b)) => {
+                self.replacement_in_progress = false;
+                self.ab_progress = None;
+                self.replacement_cancel_signal
+                    .store(false, Ordering::SeqCst);
+                self.pause_signal.store(false, Ordering::SeqCst);
+                for (config, motive) in [("A", motive_a), ("B", motive_b)] {
+                    if let Some(motive) = motive {
+                        notify(
+                            ctx,
+                            Notification::warning(format!(
+                                "A/B comparison is incomplete: configuration {} was cancelled, {}",
+                                config,
+                                motive.description()
+                            )),
+                        );
+                    }
+                }
+                self.text_projects[project_index].ab_result = Some((a, b));
+                self.restart_pending_auto_run(ctx);
+                true
+            }
+            Msg::UpdateABProgress(config, completed, total) => match &mut self.ab_progress {
+                Some(progress) => {
+                    progress[config as usize] = (completed, total);
+                    true
+                }
+                None => false,
+            },
+            Msg::CloseABComparison(project_index) => {
+                self.text_projects[project_index].ab_result = None;
+                true
+            }
+            Msg::AppendStreamedChunk(project_index, chunk, processed, total) => {
+                let project = &mut self.text_projects[project_index];
+                project.output.push_str(&chunk);
+                if let OutputStatus::InProgress {
+                    processed: p,
+                    total: t,
+                    ..
+                } = &mut project.output_status
+                {
+                    *p = processed;
+                    *t = total;
+                }
+                true
+            }
+            Msg::AppendLog(entry) => {
+                if self.execution_log.len() >= MAX_LOG_ENTRIES {
+                    self.execution_log.remove(0);
+                }
+                self.execution_log.push(entry);
+                self.execution_log_open
+            }
+            Msg::ToggleExecutionLog => {
+                self.execution_log_open = !self.execution_log_open;
+                true
+            }
+            Msg::UpdateProgress(project_index, completed, total, fired) => {
+                let project = &mut self.text_projects[project_index];
+                if let OutputStatus::InProgress {
+                    completed_steps,
+                    total_steps,
+                    fired_regex,
+                    ..
+                } = &mut project.output_status
+                {
+                    *completed_steps = completed;
+                    *total_steps = total;
+                    *fired_regex = fired;
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::UpdateEta(eta) => {
+                if !self.replacement_in_progress {
+                    return false;
+                }
+                self.replacement_eta = Some(eta);
+                true
+            }
+            Msg::PauseReplacement => {
+                if !self.replacement_in_progress {
+                    return false;
+                }
+                self.pause_signal.store(true, Ordering::SeqCst);
+                self.screen_reader_message = "Replacement paused".to_string();
+                true
+            }
+            Msg::ResumeReplacement => {
+                self.pause_signal.store(false, Ordering::SeqCst);
+                self.screen_reader_message = "Replacement resumed".to_string();
+                true
+            }
+            Msg::ShowPausedOutput(project_index, content) => {
+                if !self.pause_signal.load(Ordering::SeqCst) {
+                    return false;
+                }
+                self.text_projects[project_index].output = content;
+                true
+            }
             Msg::CancelReplacingText() => {
                 if self.replacement_in_progress {
                     self.replacement_cancel_signal.store(true, Ordering::SeqCst);
@@ -387,29 +3724,163 @@ impl Component for Model {
                     false
                 }
             }
-            Msg::FinishReplacingText(project_index, content) => {
+            Msg::FinishReplacingText(project_index, result) => {
                 self.replacement_in_progress = false;
+                self.replacement_eta = None;
                 let project = &mut self.text_projects[project_index];
-                project.output = content;
-                project.output_status = OutputStatus::Done;
+                if let Some(run) = self.current_run.take() {
+                    self.run_history.push(RunRecord::new(
+                        run,
+                        project_index,
+                        project.input.chars().count(),
+                        &result.regex_matches,
+                    ));
+                    if self.run_history.len() > stats::MAX_RUN_HISTORY {
+                        self.run_history.remove(0);
+                    }
+                }
+                project.output = result.content;
+                project.regex_durations = result.regex_durations;
+                project.output_status = if std::mem::take(&mut self.input_edited_during_run) {
+                    OutputStatus::Outdated
+                } else {
+                    OutputStatus::Done
+                };
+                // the word diff blocks the page, so large texts only get
+                // their net size change
+                project.byte_changes =
+                    if project.input.len() + project.output.len() <= diff::MAX_DIFF_BYTES {
+                        Some(diff::byte_changes(&project.input, &project.output))
+                    } else {
+                        None
+                    };
+                let duration_ms = project.replacement_duration().unwrap_or_default();
+                project.record(ProjectEvent::ReplacementFinished {
+                    at: js_sys::Date::now(),
+                    duration_ms,
+                });
+                self.screen_reader_message = "Replacement complete".to_string();
+                if project.props.webhook_url.is_some() {
+                    ctx.link()
+                        .send_message(Msg::SendOutputToWebhook(project_index));
+                }
                 self.replacement_cancel_signal
                     .store(false, Ordering::SeqCst);
+                self.pause_signal.store(false, Ordering::SeqCst);
+                self.restart_pending_auto_run(ctx);
 
                 true
             }
             Msg::CancelledReplacingText(project_index, cancel_motive, latest_content) => {
                 self.replacement_in_progress = false;
+                self.replacement_eta = None;
+                // only finished runs are kept in the history
+                self.current_run = None;
                 let project = &mut self.text_projects[project_index];
                 project.output = latest_content;
+                project.record(ProjectEvent::ReplacementCancelled {
+                    at: js_sys::Date::now(),
+                    motive: cancel_motive,
+                });
+                self.screen_reader_message =
+                    format!("Replacement cancelled: {}", cancel_motive.description());
                 project.output_status = OutputStatus::Cancelled(cancel_motive);
                 self.replacement_cancel_signal
                     .store(false, Ordering::SeqCst);
+                self.pause_signal.store(false, Ordering::SeqCst);
+                self.restart_pending_auto_run(ctx);
 
                 true
             }
         }
     }
 
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        let document = web_sys::window().and_then(|w| w.document());
+        if let Some(root) = document.as_ref().and_then(|d| d.document_element()) {
+            let theme = match self.settings.theme {
+                Theme::Light => "light",
+                Theme::Dark => "dark",
+            };
+            let _ = root.set_attribute("data-theme", theme);
+            let contrast = if self.settings.high_contrast {
+                "high"
+            } else {
+                "normal"
+            };
+            let _ = root.set_attribute("data-contrast", contrast);
+        }
+        let textarea = document
+            .as_ref()
+            .and_then(|d| d.query_selector("textarea[name='original-text']").ok())
+            .flatten();
+        match textarea {
+            Some(textarea) => {
+                let attached =
+                    matches!(&self.input_scroll_listener, Some((t, _)) if *t == textarea);
+                if !attached {
+                    let sync = ctx
+                        .link()
+                        .callback(|top| Msg::SyncScroll(TextAreaId::Input, top));
+                    let target = textarea.clone();
+                    let listener =
+                        gloo_events::EventListener::new(&textarea, "scroll", move |_| {
+                            align_input_highlight();
+                            sync.emit(target.scroll_top() as f64);
+                        });
+                    self.input_scroll_listener = Some((textarea, listener));
+                }
+                align_input_highlight();
+            }
+            None => self.input_scroll_listener = None,
+        }
+        let textarea = document
+            .as_ref()
+            .and_then(|d| d.query_selector("textarea[name='replaced-text']").ok())
+            .flatten();
+        match textarea {
+            Some(textarea) => {
+                let attached =
+                    matches!(&self.output_scroll_listener, Some((t, _)) if *t == textarea);
+                if !attached {
+                    let sync = ctx
+                        .link()
+                        .callback(|top| Msg::SyncScroll(TextAreaId::Output, top));
+                    let target = textarea.clone();
+                    let listener =
+                        gloo_events::EventListener::new(&textarea, "scroll", move |_| {
+                            sync.emit(target.scroll_top() as f64);
+                        });
+                    self.output_scroll_listener = Some((textarea, listener));
+                }
+            }
+            None => self.output_scroll_listener = None,
+        }
+
+        if self.scroll_to_output_search {
+            self.scroll_to_output_search = false;
+            let current = web_sys::window()
+                .and_then(|w| w.document())
+                .and_then(|d| d.get_element_by_id("output-search-current"));
+            if let Some(current) = current {
+                current.scroll_into_view();
+            }
+        }
+
+        if let Some(document) = &document {
+            describe_fields_by_help(document);
+        }
+
+        if let Some((step_index, regex_index)) = self.scroll_to_regex.take() {
+            let regex = web_sys::window().and_then(|w| w.document()).and_then(|d| {
+                d.get_element_by_id(&format!("step-{}-regex-{}", step_index, regex_index))
+            });
+            if let Some(regex) = regex {
+                regex.scroll_into_view();
+            }
+        }
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         use ybc::InputType;
         use ybc::NavbarFixed::Top;
@@ -420,15 +3891,47 @@ impl Component for Model {
         let link = ctx.link();
 
         let new_step = link.callback(|_| Msg::AddStep);
+        let close_all_steps = link.callback(|_| Msg::CloseAllStepEditors);
+        let open_all_steps = link.callback(|_| Msg::OpenAllStepEditors);
+        let export_markdown = link.callback(|_| Msg::ExportAsMarkdown);
+        let reset_to_defaults =
+            link.callback(|_| Msg::RequestConfirmation(PendingAction::ResetToDefaults));
 
         let active_text_project_index = self.active_text_project;
 
         let navbar = {
             let navbrand = html_nested! {<div />};
-            let navstart = html_nested! {<div />};
+            let navstart = html! {
+                <ybc::NavbarItem>
+                    <ybc::Control>
+                        <ybc::Input
+                            name={"regex-search"}
+                            value={self.regex_search_query.clone()}
+                            update={link.callback(Msg::UpdateGlobalRegexSearch)}
+                            placeholder={"Search regexes"}
+                            size={ybc::Size::Small}
+                        />
+                    </ybc::Control>
+                </ybc::NavbarItem>
+            };
             let nav_steps = {
-                let navlink = html! {"Steps"};
+                let navlink = html! {
+                    <span aria-haspopup="true" aria-expanded={self.steps_dropdown_expanded.to_string()}>
+                        {"Steps"}
+                    </span>
+                };
+                let expand = link.callback(|_: MouseEvent| Msg::SetStepsDropdownExpanded(true));
+                let collapse = link.callback(|_: MouseEvent| Msg::SetStepsDropdownExpanded(false));
+                let focus_in = link.callback(|_: FocusEvent| Msg::SetStepsDropdownExpanded(true));
+                let focus_out = link.callback(|_: FocusEvent| Msg::SetStepsDropdownExpanded(false));
                 html! {<>
+                    <div
+                        class="navbar-dropdown-state"
+                        onmouseenter={expand}
+                        onmouseleave={collapse}
+                        onfocusin={focus_in}
+                        onfocusout={focus_out}
+                    >
                     <ybc::NavbarDropdown
                         {navlink}
                         hoverable=true
@@ -439,10 +3942,77 @@ impl Component for Model {
                             {"Add Step"}
                         </ybc::Button></div>
                     </ybc::NavbarItem>
+                    <ybc::NavbarItem>
+                        <div class="buttons">
+                            <div onclick={close_all_steps}><ybc::Button
+                                classes={classes!("is-small")}
+                                disabled={self.steps_edit.is_empty()}
+                            >
+                                {"Collapse All"}
+                            </ybc::Button></div>
+                            <div onclick={open_all_steps} class="ml-1"><ybc::Button
+                                classes={classes!("is-small")}
+                                disabled={self.steps_edit.len() == self.steps.len()}
+                            >
+                                {"Expand All"}
+                            </ybc::Button></div>
+                        </div>
+                    </ybc::NavbarItem>
+                    <ybc::NavbarItem>
+                        <div class="buttons">
+                            <div onclick={link.callback(|_| Msg::SetAllStepsEnabled(false))}><ybc::Button
+                                classes={classes!("is-small")}
+                                disabled={self.steps.iter().all(|s| !s.props.enabled)}
+                            >
+                                {"Disable All"}
+                            </ybc::Button></div>
+                            <div onclick={link.callback(|_| Msg::SetAllStepsEnabled(true))} class="ml-1"><ybc::Button
+                                classes={classes!("is-small")}
+                                disabled={self.steps.iter().all(|s| s.props.enabled)}
+                            >
+                                {"Enable All"}
+                            </ybc::Button></div>
+                        </div>
+                    </ybc::NavbarItem>
+                    <ybc::NavbarItem>
+                        <div onclick={export_markdown}><ybc::Button classes={classes!("is-small")}>
+                            <span class="icon is-small">
+                                <i class="fas fa-file-download" aria-hidden="true"></i>
+                            </span>
+                            <span>
+                                {"Export as Markdown"}
+                            </span>
+                        </ybc::Button></div>
+                    </ybc::NavbarItem>
+                    <ybc::NavbarItem>
+                        <div onclick={reset_to_defaults}><ybc::Button classes={classes!("is-small", "is-danger", "is-light")}>
+                            <span class="icon is-small">
+                                <i class="fas fa-undo" aria-hidden="true"></i>
+                            </span>
+                            <span>
+                                {"Reset to Defaults"}
+                            </span>
+                        </ybc::Button></div>
+                    </ybc::NavbarItem>
 
                     { for self.steps.iter().enumerate().map(|(i, step)| {
-                        let set_enabled = link.callback(move |value| Msg::SetStepEnabled(i, value));
+                        let enabled = step.props.enabled;
+                        let toggle_enabled = link.callback(move |_| Msg::SetStepEnabled(i, !enabled));
                         let onclick = link.callback(move |_| Msg::SelectStep(i));
+                        let drag_step = move |e: DragEvent| {
+                            if let Some(data) = e.data_transfer() {
+                                if let Err(err) = data.set_data("text/plain", &i.to_string()) {
+                                    log::error!("Failed to start dragging the step: {:?}", err);
+                                }
+                            }
+                        };
+                        let drag_over_step = |e: DragEvent| e.prevent_default();
+                        let drop_step = link.batch_callback(move |e: DragEvent| {
+                            e.prevent_default();
+                            let data = e.data_transfer()?.get_data("text/plain").ok()?;
+                            let from = data.parse::<StepIndex>().ok()?;
+                            Some(Msg::MoveStepTo(from, i))
+                        });
                         html_nested!{
                             <ybc::NavbarItem
                                 tag={A}
@@ -455,30 +4025,148 @@ impl Component for Model {
                                 )}
                                 href={"#"}
                             >
-                                <ybc::Checkbox
-                                    name={format!("step-{}-enabled", i)}
-                                    checked={step.props.enabled}
-                                    update={set_enabled}
-                                />
                                 <span
-                                    {onclick}
-                                    class={"ml-1"}
+                                    class="step-drag"
+                                    draggable="true"
+                                    ondragstart={drag_step}
+                                    ondragover={drag_over_step}
+                                    ondrop={drop_step}
+                                    title="Drag to reorder the step"
                                 >
-                                    {format!(" {} - ", i + 1)}
-                                    {if step.props.title.trim().is_empty() {
-                                        "New Step"
-                                    } else {
-                                        &step.props.title
-                                    }}
+                                    <label class="checkbox">
+                                        <input
+                                            type="checkbox"
+                                            name={format!("step-{}-enabled", i)}
+                                            checked={enabled}
+                                            onclick={toggle_enabled}
+                                            aria-label={format!("Step {} enabled", i + 1)}
+                                            aria-disabled={(!enabled).to_string()}
+                                        />
+                                    </label>
+                                    <span
+                                        {onclick}
+                                        class={"ml-1"}
+                                    >
+                                        {format!(" {} - ", i + 1)}
+                                        {if step.props.title.trim().is_empty() {
+                                            "New Step"
+                                        } else {
+                                            &step.props.title
+                                        }}
+                                    </span>
+                                    <span
+                                        class={classes!("tag", "ml-2", if step.props.enabled { "is-info" } else { "is-light" })}
+                                        title={"How many regexes the step has."}
+                                    >
+                                        {step.regexes.len()}
+                                    </span>
                                 </span>
                             </ybc::NavbarItem>
                         }
                     }) }
                     </ybc::NavbarDropdown>
+                    </div>
                 </>}
             };
 
-            let navend = nav_steps;
+            let nav_templates = {
+                let has_steps_content = self
+                    .steps
+                    .iter()
+                    .any(|s| !s.props.title.trim().is_empty() || !s.regexes.is_empty());
+                let navlink = html! {<span aria-haspopup="true">{"Templates"}</span>};
+                html! {
+                    <ybc::NavbarDropdown
+                        {navlink}
+                        hoverable=true
+                        right=true
+                    >
+                    { for templates::TEMPLATES.iter().enumerate().map(|(i, template)| {
+                        let onclick = link.callback(move |_| if has_steps_content {
+                            Msg::RequestConfirmation(PendingAction::LoadTemplate(i))
+                        } else {
+                            Msg::LoadTemplate(i)
+                        });
+                        html_nested!{
+                            <ybc::NavbarItem
+                                tag={A}
+                                href={"#"}
+                            >
+                                <span {onclick}>{template.title}</span>
+                            </ybc::NavbarItem>
+                        }
+                    }) }
+                    </ybc::NavbarDropdown>
+                }
+            };
+
+            let nav_stats = html! {
+                <ybc::NavbarItem tag={A} href={"#"}>
+                    <span onclick={link.callback(|_| Msg::ShowStats(true))}>
+                        <span class="icon is-small mr-1">
+                            <i class="fas fa-chart-bar" aria-hidden="true"></i>
+                        </span>
+                        {"Stats"}
+                    </span>
+                </ybc::NavbarItem>
+            };
+
+            let nav_variables = html! {
+                <ybc::NavbarItem tag={A} href={"#"}>
+                    <span onclick={link.callback(|_| Msg::ShowVariables(true))}>
+                        <span class="icon is-small mr-1">
+                            <i class="fas fa-dollar-sign" aria-hidden="true"></i>
+                        </span>
+                        {"Variables"}
+                    </span>
+                </ybc::NavbarItem>
+            };
+
+            let nav_settings = html! {
+                <ybc::NavbarItem tag={A} href={"#"}>
+                    <span onclick={link.callback(|_| Msg::OpenSettings)}>
+                        <span class="icon is-small mr-1">
+                            <i class="fas fa-cog" aria-hidden="true"></i>
+                        </span>
+                        {"Settings"}
+                    </span>
+                </ybc::NavbarItem>
+            };
+
+            let nav_theme = html! {
+                <ybc::NavbarItem tag={A} href={"#"}>
+                    <span onclick={link.callback(|_| Msg::ToggleDarkMode)}>
+                        <span class="icon is-small" title="Toggle dark mode">
+                            <i class={classes!("fas", match self.settings.theme {
+                                Theme::Light => "fa-moon",
+                                Theme::Dark => "fa-sun",
+                            })}></i>
+                        </span>
+                        <span class="is-sr-only">{"Toggle dark mode"}</span>
+                    </span>
+                </ybc::NavbarItem>
+            };
+
+            let nav_cheatsheet = html! {
+                <ybc::NavbarItem tag={A} href={"#"}>
+                    <span onclick={link.callback(|_| Msg::ToggleCheatsheet)}>
+                        <span class="icon is-small mr-1">
+                            <i class="fas fa-question-circle" aria-hidden="true"></i>
+                        </span>
+                        {"Cheatsheet"}
+                    </span>
+                </ybc::NavbarItem>
+            };
+
+            let navend = html! {<>
+                {nav_theme}
+                {nav_cheatsheet}
+                {nav_settings}
+                {nav_variables}
+                {nav_stats}
+                {nav_templates}
+                {nav_steps}
+            </>};
 
             html_nested! {
                 <ybc::Navbar
@@ -497,6 +4185,71 @@ impl Component for Model {
             }
         };
 
+        let search_results = {
+            let query = self.regex_search_query.trim().to_lowercase();
+            let results: Vec<(StepIndex, RegexIndex)> = if query.is_empty() {
+                vec![]
+            } else {
+                self.steps
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, step)| {
+                        step.regexes.iter().enumerate().map(move |(j, r)| (i, j, r))
+                    })
+                    .filter(|(_, _, r)| {
+                        let pattern = match &r.r#match {
+                            Ok(re) => re.as_str(),
+                            Err(s) => s.as_str(),
+                        };
+                        [r.title.as_str(), pattern, r.replace.as_str()]
+                            .iter()
+                            .any(|field| field.to_lowercase().contains(&query))
+                    })
+                    .map(|(i, j, _)| (i, j))
+                    .collect()
+            };
+            html! {
+                if !query.is_empty() {
+                    <ybc::Box>
+                        <p class="has-text-weight-bold mb-2">{"Search Results"}</p>
+                        if results.is_empty() {
+                            <p class="is-size-7">{"No regex matches the search."}</p>
+                        }
+                        <ul>
+                        { for results.iter().map(|&(i, j)| {
+                            let step = &self.steps[i];
+                            let r = &step.regexes[j];
+                            let step_title = if step.props.title.trim().is_empty() {
+                                "New Step"
+                            } else {
+                                &step.props.title
+                            };
+                            let pattern = match &r.r#match {
+                                Ok(re) => re.as_str(),
+                                Err(s) => s.as_str(),
+                            };
+                            html! {
+                                <li>
+                                    <a onclick={link.callback(move |_| Msg::OpenStepEditor(i))}>
+                                        {format!("Step {} - {}", i + 1, step_title)}
+                                    </a>
+                                    {" / "}
+                                    <a onclick={link.callback(move |_| Msg::JumpToRegex(i, j))}>
+                                        {format!("Regex {}", j + 1)}
+                                        if !r.title.trim().is_empty() {
+                                            {format!(" - {}", r.title)}
+                                        }
+                                    </a>
+                                    <code class="ml-2 is-size-7">{pattern}</code>
+                                </li>
+                            }
+                        })}
+                        </ul>
+                    </ybc::Box>
+                }
+            }
+        };
+
         let edit_steps = {
             html_nested! {
                 { for self.steps_edit.iter().map(|i| {
@@ -504,9 +4257,24 @@ impl Component for Model {
                     let step = self.steps.get(i).unwrap();
                     let total_steps = self.steps.len();
                     let total_regexes = step.regexes.len();
+                    let duplicates = step::find_duplicate_regexes(step);
+                    let conflicts = step::detect_potential_conflicts(step);
                     let update_step_title = link.callback(move |t| Msg::UpdateStepTitle(i, t));
+                    let update_step_description = link.callback(move |d| Msg::UpdateStepDescription(i, d));
+                    let set_run_only_if_prev_changed = link.callback(move |value| Msg::SetStepRunOnlyIfPrevChanged(i, value));
+                    let set_stop_on_first_match = link.callback(move |value| Msg::SetStepStopOnFirstMatch(i, value));
+                    let set_virtual_sort = link.batch_callback(move |value: String| {
+                        let option = value.parse::<usize>().ok().and_then(|k| VirtualSort::OPTIONS.get(k));
+                        option.map(|(virtual_sort, _)| Msg::SetStepVirtualSort(i, *virtual_sort))
+                    });
                     let add_regex = link.callback(move |_| Msg::AddRegex(i));
                     let close_step = link.callback(move |_| Msg::SelectStep(i));
+                    let merge_step = link.callback(move |_| Msg::MergeSteps(i));
+                    let export_regexes = link.callback(move |_| Msg::ExportStepAsText(i));
+                    let export_tool_json = link.callback(move |_| Msg::ExportStepAsToolJson(i));
+                    let import_regexes = read_file_callback(link, move |text| Msg::ImportRegexesFromText(i, text));
+                    let paste_regexes = link.callback(move |_| Msg::PasteRegexesFromClipboard(i));
+                    let paste_json_regexes = link.callback(move |_| Msg::PasteRegexFromJsonClipboard(i));
                     html_nested!{
 
                         <ybc::Columns
@@ -522,6 +4290,22 @@ impl Component for Model {
                             >
 
                                 {format!("Step {}/{}", i + 1, total_steps)}
+                                if total_regexes > 1 {
+                                    <span class="step-jump ml-auto mr-2" title="Go to regex…">
+                                        <ybc::Input
+                                            name={format!("step-{}-jump-to-regex", i)}
+                                            value={""}
+                                            update={link.batch_callback(move |value: String| {
+                                                let number = value.trim().parse::<usize>().ok();
+                                                number
+                                                    .filter(|&k| 1 <= k && k <= total_regexes)
+                                                    .map(|k| Msg::JumpToRegex(i, k - 1))
+                                            })}
+                                            placeholder={"Go to regex…"}
+                                            size={ybc::Size::Small}
+                                        />
+                                    </span>
+                                }
                                 <ybc::Delete
                                     tag={"button"}
                                     onclick={close_step}
@@ -531,23 +4315,167 @@ impl Component for Model {
                             <ybc::MessageBody
                             >
 
+                                <div class="buttons are-small mb-2">
+                                    <a onclick={link.callback(move |_| Msg::SetAllRegexesEnabled(i, true))}><ybc::Button
+                                        disabled={step.regexes.iter().all(|r| r.enabled)}
+                                    >
+                                        {"Enable all regexes"}
+                                    </ybc::Button></a>
+                                    <a onclick={link.callback(move |_| Msg::SetAllRegexesEnabled(i, false))} class="ml-1"><ybc::Button
+                                        disabled={step.regexes.iter().all(|r| !r.enabled)}
+                                    >
+                                        {"Disable all regexes"}
+                                    </ybc::Button></a>
+                                    <a onclick={link.callback(move |_| Msg::TestAllRegexesInStep(i))} class="ml-1"><ybc::Button
+                                        disabled={!step.regexes.iter().any(|r| r.enabled && !r.test_cases.is_empty())}
+                                    >
+                                        {"Test all regexes"}
+                                    </ybc::Button></a>
+                                    {{
+                                        let results = step.regexes.iter().filter(|r| r.enabled).filter_map(|r| r.test_cases_passed());
+                                        let (passed, failed) = results.fold((0, 0), |(p, f), passed| if passed { (p + 1, f) } else { (p, f + 1) });
+                                        let untested = step.regexes.iter().filter(|r| r.enabled && r.test_cases.is_empty()).count();
+                                        if passed + failed == 0 {
+                                            html! {}
+                                        } else {
+                                            html! {
+                                                <span class={classes!("is-size-7", "ml-2", if failed == 0 { "has-text-success" } else { "has-text-danger" })}>
+                                                    {format!("{} regexes passed, {} regexes failed", passed, failed)}
+                                                    if untested > 0 {
+                                                        {format!(", {} regexes have no test cases", untested)}
+                                                    }
+                                                </span>
+                                            }
+                                        }
+                                    }}
+                                </div>
+
+                                <ybc::Field
+                                    label={"Step Name"}
+                                    grouped=true
+                                >
+                                <ybc::Control
+                                    tag={"div"}
+                                    classes={classes!("has-icons-left")}
+                                    expanded=true
+                                >
+                                <ybc::Input
+                                    name={format!("step-{}-title", i)}
+                                    value={step.props.title.clone()}
+                                    update={update_step_title}
+                                    placeholder={r#"Optionally add a step name. Defaults to "New Step"."#}
+                                />
+                                <span class="icon is-small is-left">
+                                    <i class="fas fa-info" aria-hidden="true" />
+                                </span>
+                                </ybc::Control>
+                                <ybc::Control>
+                                <span title="Lower priorities run first. Steps of the same priority run in declaration order.">
+                                <ybc::Input
+                                    name={format!("step-{}-priority", i)}
+                                    value={step.props.priority.to_string()}
+                                    update={link.batch_callback(move |value: String| {
+                                        value.trim().parse().ok().map(|priority| Msg::SetStepPriority(i, priority))
+                                    })}
+                                    placeholder={"Priority"}
+                                    classes={classes!("step-priority")}
+                                />
+                                </span>
+                                </ybc::Control>
+                                </ybc::Field>
+
+                                <details class="mb-3" open={!step.props.description.is_empty()}>
+                                    <summary class="is-size-7">{"Description"}</summary>
+                                    <ybc::TextArea
+                                        name={format!("step-{}-description", i)}
+                                        value={step.props.description.clone()}
+                                        update={update_step_description}
+                                        placeholder={"Optionally explain what this step accomplishes."}
+                                        rows=2
+                                        size={ybc::Size::Small}
+                                    />
+                                </details>
+
+                                <ybc::Field
+                                    label={"Order by"}
+                                    help={"In which order the regexes are tried on each iteration. Ties keep their declaration order."}
+                                >
+                                <ybc::Control>
+                                <ybc::Select
+                                    name={format!("step-{}-virtual-sort", i)}
+                                    value={VirtualSort::OPTIONS
+                                        .iter()
+                                        .position(|(virtual_sort, _)| *virtual_sort == step.props.virtual_sort)
+                                        .unwrap_or_default()
+                                        .to_string()}
+                                    update={set_virtual_sort}
+                                >
+                                    { for VirtualSort::OPTIONS.iter().enumerate().map(|(k, (virtual_sort, label))| html! {
+                                        <option
+                                            value={k.to_string()}
+                                            selected={*virtual_sort == step.props.virtual_sort}
+                                        >
+                                            {label}
+                                        </option>
+                                    })}
+                                </ybc::Select>
+                                </ybc::Control>
+                                </ybc::Field>
+
+                                if i != 0 {
+                                    <ybc::Field>
+                                        <ybc::Checkbox
+                                            name={format!("step-{}-run-only-if-prev-changed", i)}
+                                            checked={step.props.run_only_if_prev_changed}
+                                            update={set_run_only_if_prev_changed}
+                                        >
+                                            {" Only run if the previous step changed the text"}
+                                        </ybc::Checkbox>
+                                    </ybc::Field>
+                                }
+
+                                <ybc::Field help={"Only the first regex that matches is applied, and only once."}>
+                                    <ybc::Checkbox
+                                        name={format!("step-{}-stop-on-first-match", i)}
+                                        checked={step.props.stop_on_first_match}
+                                        update={set_stop_on_first_match}
+                                    >
+                                        {" Stop on the first match"}
+                                    </ybc::Checkbox>
+                                </ybc::Field>
+
                                 <ybc::Field
-                                    label={"Step Name"}
-                                >
-                                <ybc::Control
-                                    tag={"div"}
-                                    classes={classes!("has-icons-left")}
+                                    label={"Pause between regexes (ms)"}
+                                    help={"Pauses after each substitution, showing which regex fired, to follow the replacement in slow motion."}
                                 >
-                                <ybc::Input
-                                    name={format!("step-{}-title", i)}
-                                    value={step.props.title.clone()}
-                                    update={update_step_title}
-                                    placeholder={r#"Optionally add a step name. Defaults to "New Step"."#}
-                                />
-                                <span class="icon is-small is-left">
-                                    <i class="fas fa-info" />
-                                </span>
-                                </ybc::Control>
+                                    <ybc::Input
+                                        name={format!("step-{}-pause-between-regexes", i)}
+                                        value={step.props.pause_between_regexes_ms.map(|ms| ms.to_string()).unwrap_or_default()}
+                                        update={link.callback(move |value: String| Msg::SetStepPauseBetweenRegexes(i, value.trim().parse().ok()))}
+                                        placeholder={"No pause"}
+                                        size={ybc::Size::Small}
+                                    />
+                                </ybc::Field>
+
+                                <ybc::Field label={"A/B comparison"} grouped=true>
+                                    <ybc::Control>
+                                    <ybc::Checkbox
+                                        name={format!("step-{}-in-config-a", i)}
+                                        checked={step.props.in_config_a}
+                                        update={link.callback(move |value| Msg::SetStepInConfig(i, AbConfig::A, value))}
+                                    >
+                                        {" In configuration A"}
+                                    </ybc::Checkbox>
+                                    </ybc::Control>
+                                    <ybc::Control>
+                                    <ybc::Checkbox
+                                        name={format!("step-{}-in-config-b", i)}
+                                        checked={step.props.in_config_b}
+                                        update={link.callback(move |value| Msg::SetStepInConfig(i, AbConfig::B, value))}
+                                    >
+                                        {" In configuration B"}
+                                    </ybc::Checkbox>
+                                    </ybc::Control>
                                 </ybc::Field>
 
 
@@ -563,159 +4491,707 @@ impl Component for Model {
                             use ybc::Size::Small;
                             let update_regex_title = link.callback(move |t| Msg::UpdateRegexTitle(i, j, t));
                             let update_regex_match = link.callback(move |s| Msg::UpdateRegexSearch(i, j, s));
+                            let highlight_matches = link.callback(move |_: FocusEvent| Msg::HighlightRegexMatches(Some((i, j))));
+                            let clear_highlight = link.callback(|_: FocusEvent| Msg::HighlightRegexMatches(None));
                             let update_regex_replace = link.callback(move |s| Msg::UpdateRegexReplacement(i, j, s));
-                            let delete_regex = link.callback(move |_| Msg::DeleteRegex(i, j, true));
+                            let set_skip_if_prev_no_match = link.callback(move |value| Msg::SetRegexSkipIfPrevNoMatch(i, j, value));
+                            let set_apply_once_per_line = link.callback(move |value| Msg::SetRegexApplyOncePerLine(i, j, value));
+                            let flags = r.flags;
+                            let set_case_insensitive = link.callback(move |case_insensitive| Msg::UpdateRegexFlags(i, j, RegexFlags { case_insensitive, ..flags }));
+                            let set_multiline = link.callback(move |multiline| Msg::UpdateRegexFlags(i, j, RegexFlags { multiline, ..flags }));
+                            let set_dot_all = link.callback(move |dot_all| Msg::UpdateRegexFlags(i, j, RegexFlags { dot_all, ..flags }));
+                            let set_unicode_word_boundary = link.callback(move |unicode_word_boundary| Msg::UpdateRegexFlags(i, j, RegexFlags { unicode_word_boundary, ..flags }));
+                            let set_verbose = link.callback(move |verbose| Msg::UpdateRegexFlags(i, j, RegexFlags { verbose, ..flags }));
+                            let delete_regex = link.callback(move |_| Msg::RequestConfirmation(PendingAction::DeleteRegex(i, j)));
+                            let duplicate_regex = link.callback(move |_| Msg::DuplicateRegex(i, j));
+                            let other_steps = self.steps.iter().enumerate().filter(|(k, _)| *k != i).map(|(k, step)| {
+                                let name = if step.props.title.trim().is_empty() {
+                                    format!("{} - New Step", k + 1)
+                                } else {
+                                    format!("{} - {}", k + 1, step.props.title)
+                                };
+                                (k, name)
+                            }).collect::<Vec<_>>();
                             let move_regex_up = link.callback(move |_| Msg::MoveRegex(i, j, MoveDirection::Up));
                             let move_regex_down = link.callback(move |_| Msg::MoveRegex(i, j, MoveDirection::Down));
+                            let split_step = link.callback(move |_| Msg::SplitStep(i, j));
+                            let add_test_case = link.callback(move |_| Msg::AddTestCase(i, j));
+                            let run_test_cases = link.callback(move |_| Msg::RunTestCases(i, j));
+                            let show_diagram = link.callback(move |_| Msg::ShowRegexDiagram(i, j));
+                            let benchmark_regex = link.callback(move |_| Msg::BenchmarkRegex(i, j));
+                            let analyze_complexity = link.callback(move |_| Msg::AnalyzeRegexComplexity(i, j));
                             let (re_text, re_error) = match &r.r#match {
                                 Ok(re) => (re.to_string(), None),
                                 Err(re) => (re.clone(), r.match_parse_error.clone())
                             };
+                            let explanation = explain::explain_regex(&re_text, r.flags);
+                            let group_name = match &self.new_group_name {
+                                Some((position, name)) if *position == (i, j) => name.clone(),
+                                _ => String::new(),
+                            };
+                            let is_active = self.active_regex == Some((i, j));
+                            let collapsed = matches!(self.active_regex, Some((k, _)) if k == i) && !is_active;
+                            let drag_regex = move |e: DragEvent| {
+                                if let Some(data) = e.data_transfer() {
+                                    if let Err(err) = data.set_data("text/plain", &format!("{}:{}", i, j)) {
+                                        log::error!("Failed to start dragging the regex: {:?}", err);
+                                    }
+                                }
+                            };
+                            let drag_over_regex = |e: DragEvent| e.prevent_default();
+                            // only regexes dragged from the same step are dropped
+                            let drop_regex = link.batch_callback(move |e: DragEvent| {
+                                e.prevent_default();
+                                let data = e.data_transfer()?.get_data("text/plain").ok()?;
+                                let (from_step, from) = data.split_once(':')?;
+                                let from_step = from_step.parse::<StepIndex>().ok()?;
+                                let from = from.parse::<RegexIndex>().ok()?;
+                                (from_step == i).then(|| Msg::MoveRegexTo(i, from, j))
+                            });
                             html_nested! {
-                                <ybc::Tile ctx={Child} classes={classes!("box")}>
-                                    <ybc::Subtitle
-                                        size={ybc::HeaderSize::Is6}
-                                    >
-                                        {format!("Regex {}/{}", j + 1, total_regexes)}
-                                    </ybc::Subtitle>
-
-                                    <ybc::Field grouped=true>
-                                        <a onclick={move_regex_up}><ybc::Button
-                                            classes={classes!("is-small")}
-                                            disabled={j == 0}
-                                        >
+                                <>
+                                if j != 0 {
+                                    <div class="split-step has-text-centered">
+                                        <a onclick={split_step}><ybc::Button classes={classes!("is-small", "is-text")}>
                                             <span class="icon is-small">
-                                                <i class="fas fa-arrow-up"></i>
+                                                <i class="fas fa-cut" aria-hidden="true"></i>
                                             </span>
-                                        </ybc::Button></a>
-                                        <a onclick={move_regex_down}><ybc::Button
-                                            classes={classes!("is-small")}
-                                            disabled={j + 1 == total_regexes}
-                                        >
-                                            <span class="icon is-small">
-                                                <i class="fas fa-arrow-down"></i>
+                                            <span>
+                                                {"Split step here"}
                                             </span>
                                         </ybc::Button></a>
-                                        <a onclick={delete_regex}><ybc::Button classes={classes!("is-small")}>
-                                            <span class="icon is-small">
-                                                <i class="fas fa-trash"></i>
+                                    </div>
+                                }
+                                <div
+                                    class="regex-drop-target"
+                                    ondragover={drag_over_regex}
+                                    ondrop={drop_regex}
+                                >
+                                    <ybc::Tile ctx={Child} classes={classes!("box")}>
+                                        <ybc::Subtitle
+                                            size={ybc::HeaderSize::Is6}
+                                            classes={classes!((!r.enabled).then(|| "has-text-grey"))}
+                                        >
+                                            <ybc::Checkbox
+                                                name={format!("step-{}-regex-{}-enabled", i, j)}
+                                                checked={r.enabled}
+                                                update={link.callback(move |value| Msg::SetRegexEnabled(i, j, value))}
+                                            />
+                                            <span
+                                                class="icon is-small drag-handle ml-1"
+                                                draggable="true"
+                                                ondragstart={drag_regex}
+                                                title="Drag to reorder the regex"
+                                            >
+                                                <i class="fas fa-grip-vertical" aria-hidden="true"></i>
                                             </span>
-                                        </ybc::Button></a>
-                                    </ybc::Field>
+                                            <a
+                                                id={format!("step-{}-regex-{}", i, j)}
+                                                onclick={link.callback(move |_| Msg::SetActiveRegex(i, j))}
+                                                title={if is_active { "Show every regex of the step" } else { "Show only this regex of the step" }}
+                                            >
+                                                {format!(" Regex {}/{}", j + 1, total_regexes)}
+                                            </a>
+                                            if collapsed && !r.title.trim().is_empty() {
+                                                {format!(" - {}", r.title)}
+                                            }
+                                            if !r.enabled {
+                                                <span class="tag is-light ml-2">{"disabled"}</span>
+                                            }
+                                            if let Some(passed) = r.test_cases_passed() {
+                                                <span class={classes!("tag", "ml-2", if passed { "is-success" } else { "is-danger" })}>
+                                                    {if passed { "tests passed" } else { "tests failed" }}
+                                                </span>
+                                            }
+                                            if step::is_noop_replacement(&re_text, &r.replace, r.flags) {
+                                                <span
+                                                    class="tag is-warning ml-2"
+                                                    title="The replacement is always the same as the matched text, so this regex never changes the text."
+                                                >
+                                                    {"no-op"}
+                                                </span>
+                                            }
+                                            if let Some((original, _)) = duplicates.iter().find(|(_, d)| *d == j) {
+                                                <span
+                                                    class="icon has-text-warning ml-2"
+                                                    role="img"
+                                                    aria-label="Unreachable regex"
+                                                    title={format!(
+                                                        "This regex has the same match as regex {}, which has a higher priority, so it is never reached.",
+                                                        original + 1
+                                                    )}
+                                                >
+                                                    <i class="fas fa-exclamation-triangle" aria-hidden="true"></i>
+                                                </span>
+                                            }
+                                            { for conflicts.iter().filter(|(a, _)| *a == j).map(|(_, b)| html! {
+                                                <span
+                                                    class="icon has-text-warning ml-2"
+                                                    role="img"
+                                                    aria-label="Possible replacement cycle"
+                                                    title={if *b == j {
+                                                        "The replacement may be matched again by this same regex, which could cause a replacement cycle.".to_string()
+                                                    } else {
+                                                        format!("The replacement may be matched by regex {}, which could cause a replacement cycle.", b + 1)
+                                                    }}
+                                                >
+                                                    <i class="fas fa-sync-alt" aria-hidden="true"></i>
+                                                </span>
+                                            })}
+                                            if let Some(count) = r.match_count {
+                                                <span
+                                                    class={classes!("tag", "ml-2", if count > 0 { "is-info" } else { "is-light" })}
+                                                    title={"Matches on the input text, before any replacement."}
+                                                >
+                                                    {format!("{} matches", count)}
+                                                </span>
+                                            }
+                                            if r.has_match_on_input == Some(false) {
+                                                <span
+                                                    class="tag is-light has-text-grey ml-2"
+                                                    title={"This regex doesn't match anywhere in the input text, before any replacement."}
+                                                >
+                                                    {"no matches on current input"}
+                                                </span>
+                                            }
+                                            if let Some(avg_ms) = r.avg_ms {
+                                                <span
+                                                    class={classes!("tag", "ml-2", if avg_ms > step::SLOW_MATCH_MS { "is-warning" } else { "is-light" })}
+                                                    title={if avg_ms > step::SLOW_MATCH_MS {
+                                                        "Average match time on the input text. This regex is slow, it may be backtracking catastrophically."
+                                                    } else {
+                                                        "Average match time on the input text."
+                                                    }}
+                                                >
+                                                    {format!("⚡ {:.2}ms", avg_ms)}
+                                                </span>
+                                            }
+                                            if r.complexity_risk == Some(true) {
+                                                <span
+                                                    class="tag is-danger ml-2"
+                                                    title="The match time grows super-linearly with the input length, so a long input could make the replacement hang."
+                                                >
+                                                    {"⚠ ReDoS risk"}
+                                                </span>
+                                            }
+                                        </ybc::Subtitle>
 
-                                    <ybc::Field
-                                        label={"Regex Description"}
-                                        label_classes={classes!("is-small")}
-                                    >
-                                    <ybc::Control
-                                        tag={"div"}
-                                        classes={classes!("has-icons-left")}
-                                    >
-                                    <ybc::Input
-                                        name={format!("step-{}-regex-{}-title", i, j)}
-                                        value={r.title.clone()}
-                                        update={update_regex_title}
-                                        placeholder={r#"Optionally add a regex description."#}
-                                        size={Small}
-                                    />
-                                    <span class="icon is-small is-left">
-                                        <i class="fas fa-info" />
-                                    </span>
-                                    </ybc::Control>
-                                    </ybc::Field>
-                                    <ybc::Field
-                                        label={"Regex Match"}
-                                        label_classes={classes!("is-small")}
-                                        help={
-                                            if let Some(err) = &re_error {
-                                                let err = err.to_string();
-                                                if err.trim().is_empty() {
-                                                    "unknown error".to_string()
-                                                } else {
-                                                    err
+                                        if !collapsed {
+                                            <>
+                                            <ybc::Field grouped=true>
+                                                <a onclick={move_regex_up}><ybc::Button
+                                                    classes={classes!("is-small")}
+                                                    disabled={j == 0}
+                                                >
+                                                    <span class="icon is-small">
+                                                        <i class="fas fa-arrow-up" aria-hidden="true"></i>
+                                                    </span>
+                                                    <span class="is-sr-only">{"Move the regex up"}</span>
+                                                </ybc::Button></a>
+                                                <a onclick={move_regex_down}><ybc::Button
+                                                    classes={classes!("is-small")}
+                                                    disabled={j + 1 == total_regexes}
+                                                >
+                                                    <span class="icon is-small">
+                                                        <i class="fas fa-arrow-down" aria-hidden="true"></i>
+                                                    </span>
+                                                    <span class="is-sr-only">{"Move the regex down"}</span>
+                                                </ybc::Button></a>
+                                                <a onclick={show_diagram}><ybc::Button
+                                                    classes={classes!("is-small")}
+                                                    disabled={re_error.is_some()}
+                                                >
+                                                    <span class="icon is-small" title="Show the railroad diagram">
+                                                        <i class="fas fa-project-diagram" aria-hidden="true"></i>
+                                                    </span>
+                                                    <span class="is-sr-only">{"Show the railroad diagram"}</span>
+                                                </ybc::Button></a>
+                                                <a onclick={benchmark_regex}><ybc::Button
+                                                    classes={classes!("is-small")}
+                                                    disabled={re_error.is_some() || active_text_project_index.is_none()}
+                                                >
+                                                    <span class="icon is-small" title="Benchmark the match time on the input text">
+                                                        <i class="fas fa-tachometer-alt" aria-hidden="true"></i>
+                                                    </span>
+                                                    <span class="is-sr-only">{"Benchmark the match time on the input text"}</span>
+                                                </ybc::Button></a>
+                                                <a onclick={analyze_complexity}><ybc::Button
+                                                    classes={classes!("is-small")}
+                                                    disabled={re_error.is_some()}
+                                                >
+                                                    <span class="icon is-small" title="Check for catastrophic backtracking on longer inputs">
+                                                        <i class="fas fa-bomb" aria-hidden="true"></i>
+                                                    </span>
+                                                    <span class="is-sr-only">{"Check for catastrophic backtracking on longer inputs"}</span>
+                                                </ybc::Button></a>
+                                                <div class="dropdown is-hoverable">
+                                                    <div class="dropdown-trigger" aria-haspopup="true">
+                                                        <ybc::Button classes={classes!("is-small")}>
+                                                            <span class="icon is-small">
+                                                                <i class="fas fa-angle-down" aria-hidden="true"></i>
+                                                            </span>
+                                                            <span class="is-sr-only">{"More regex actions"}</span>
+                                                        </ybc::Button>
+                                                    </div>
+                                                    <div class="dropdown-menu">
+                                                        <div class="dropdown-content">
+                                                            <a class="dropdown-item" onclick={duplicate_regex}>
+                                                                {"Duplicate"}
+                                                            </a>
+                                                            if !other_steps.is_empty() {
+                                                                <hr class="dropdown-divider" />
+                                                            }
+                                                            { for other_steps.iter().map(|(k, name)| {
+                                                                let k = *k;
+                                                                html! {
+                                                                    <a class="dropdown-item" onclick={link.callback(move |_| Msg::MoveRegexToStep(i, j, k))}>
+                                                                        {format!("Move to step {}", name)}
+                                                                    </a>
+                                                                }
+                                                            })}
+                                                            { for other_steps.iter().map(|(k, name)| {
+                                                                let k = *k;
+                                                                html! {
+                                                                    <a class="dropdown-item" onclick={link.callback(move |_| Msg::CopyRegexToStep(i, j, k))}>
+                                                                        {format!("Copy to step {}", name)}
+                                                                    </a>
+                                                                }
+                                                            })}
+                                                            <hr class="dropdown-divider" />
+                                                            <a class="dropdown-item has-text-danger" onclick={delete_regex}>
+                                                                <span class="icon is-small mr-1">
+                                                                    <i class="fas fa-trash" aria-hidden="true"></i>
+                                                                </span>
+                                                                {"Delete"}
+                                                            </a>
+                                                        </div>
+                                                    </div>
+                                                </div>
+                                            </ybc::Field>
+
+                                            <ybc::Field
+                                                label={"Regex Description"}
+                                                label_classes={classes!("is-small")}
+                                            >
+                                            <ybc::Control
+                                                tag={"div"}
+                                                classes={classes!("has-icons-left")}
+                                            >
+                                            <ybc::Input
+                                                name={format!("step-{}-regex-{}-title", i, j)}
+                                                value={r.title.clone()}
+                                                update={update_regex_title}
+                                                placeholder={r#"Optionally add a regex description."#}
+                                                size={Small}
+                                            />
+                                            <span class="icon is-small is-left">
+                                                <i class="fas fa-info" aria-hidden="true" />
+                                            </span>
+                                            </ybc::Control>
+                                            </ybc::Field>
+                                            <ybc::Field
+                                                label={"Regex Match"}
+                                                label_classes={classes!("is-small")}
+                                                help={
+                                                    if let Some(err) = &re_error {
+                                                        let err = err.to_string();
+                                                        if err.trim().is_empty() {
+                                                            "unknown error".to_string()
+                                                        } else {
+                                                            err
+                                                        }
+                                                    } else if !re_text.is_empty() {
+                                                        "".to_string()
+                                                    } else {
+                                                        "No match defined. This regex will be ignored.".to_string()
+                                                    }
                                                 }
-                                            } else if !re_text.is_empty() {
-                                                "".to_string()
-                                            } else {
-                                                "No match defined. This regex will be ignored.".to_string()
-                                            }
-                                        }
-                                    >
-                                    <ybc::Control
-                                        tag={"div"}
-                                        classes={classes!("has-icons-left")}
-                                    >
-                                    <ybc::Input
-                                        name={format!("step-{}-regex-{}-match", i, j)}
-                                        value={re_text}
-                                        update={update_regex_match}
-                                        placeholder={r#"What to try to match. Eg. "ABC"."#}
-                                        classes={classes!(
-                                            if re_error.is_some() {
-                                                "is-danger"
-                                            } else if !re_text.is_empty() {
-                                                "is-success"
+                                            >
+                                            <div onfocusin={highlight_matches} onfocusout={clear_highlight}>
+                                            if flags.verbose {
+                                                <ybc::TextArea
+                                                    name={format!("step-{}-regex-{}-match", i, j)}
+                                                    value={re_text.clone()}
+                                                    update={update_regex_match}
+                                                    placeholder={"What to try to match, over many lines. Whitespace is ignored and # starts a comment."}
+                                                    classes={classes!(
+                                                        "is-family-monospace",
+                                                        if re_error.is_some() {
+                                                            "is-danger"
+                                                        } else if !re_text.is_empty() {
+                                                            "is-success"
+                                                        } else {
+                                                            ""
+                                                        }
+                                                    )}
+                                                    rows=4
+                                                    size={Small}
+                                                />
                                             } else {
-                                                ""
+                                                <ybc::Control
+                                                    tag={"div"}
+                                                    classes={classes!("has-icons-left", "regex-highlight-control")}
+                                                >
+                                                <pre class="regex-highlight" aria-hidden="true">
+                                                    {highlight::regex_highlight(&re_text)}
+                                                </pre>
+                                                <ybc::Input
+                                                    name={format!("step-{}-regex-{}-match", i, j)}
+                                                    value={re_text}
+                                                    update={update_regex_match}
+                                                    placeholder={r#"What to try to match. Eg. "ABC"."#}
+                                                    classes={classes!(
+                                                        "regex-highlight-input",
+                                                        if re_error.is_some() {
+                                                            "is-danger"
+                                                        } else if !re_text.is_empty() {
+                                                            "is-success"
+                                                        } else {
+                                                            ""
+                                                        }
+                                                    )}
+                                                    size={Small}
+                                                />
+                                                <span class="icon is-small is-left">
+                                                    <i class="fas fa-search" aria-hidden="true" />
+                                                </span>
+                                                </ybc::Control>
                                             }
-                                        )}
-                                        size={Small}
-                                    />
-                                    <span class="icon is-small is-left">
-                                        <i class="fas fa-search" />
-                                    </span>
-                                    </ybc::Control>
-                                    </ybc::Field>
-                                    <ybc::Field
-                                        label={"Regex Replacement"}
-                                        label_classes={classes!("is-small")}
-                                        help={
-                                            if r.replace.is_empty() {
-                                                "The replacement is empty. This will erase the matched content."
-                                            } else {
-                                                ""
+                                            </div>
+                                            </ybc::Field>
+                                            <ybc::Field>
+                                            <ybc::Control>
+                                            <ybc::Select
+                                                name={format!("step-{}-regex-{}-common-pattern", i, j)}
+                                                value={""}
+                                                update={link.batch_callback(move |key: String| {
+                                                    patterns::COMMON_PATTERNS
+                                                        .iter()
+                                                        .find(|(k, _, _)| *k == key)
+                                                        .map(|(k, _, _)| Msg::InsertCommonPattern(i, j, k))
+                                                })}
+                                                size={ybc::Size::Small}
+                                            >
+                                                <option value="" selected=true>{"Insert a common pattern…"}</option>
+                                                { for patterns::COMMON_PATTERNS.iter().map(|(key, description, _)| html! {
+                                                    <option value={*key}>{*description}</option>
+                                                })}
+                                            </ybc::Select>
+                                            </ybc::Control>
+                                            </ybc::Field>
+                                            <ybc::Field addons=true>
+                                                <ybc::Control>
+                                                    <ybc::Input
+                                                        name={format!("step-{}-regex-{}-group-name", i, j)}
+                                                        value={group_name.clone()}
+                                                        update={link.callback(move |name| Msg::UpdateNewGroupName(i, j, name))}
+                                                        placeholder={"Group name"}
+                                                        size={ybc::Size::Small}
+                                                    />
+                                                </ybc::Control>
+                                                <ybc::Control>
+                                                    <a onclick={link.callback({
+                                                        let name = group_name.clone();
+                                                        move |_| Msg::AddNamedGroup(i, j, name.clone())
+                                                    })}><ybc::Button
+                                                        classes={classes!("is-small")}
+                                                        disabled={!step::is_valid_group_name(group_name.trim())}
+                                                    >
+                                                        <span title="Appends (?P<name>) to the match and ${name} to the replacement">
+                                                            {"+ Add named group"}
+                                                        </span>
+                                                    </ybc::Button></a>
+                                                </ybc::Control>
+                                                <ybc::Control>
+                                                    <a onclick={link.batch_callback(move |_| {
+                                                        wrap_input_selection(&format!("step-{}-regex-{}-match", i, j), "(?:", ")")
+                                                            .map(|search| Msg::UpdateRegexSearch(i, j, search))
+                                                    })}><ybc::Button classes={classes!("is-small")}>
+                                                        <span title="Wrap the selected text of the match in a non-capturing group">
+                                                            {"(?:…)"}
+                                                        </span>
+                                                    </ybc::Button></a>
+                                                </ybc::Control>
+                                                { for r.group_names().into_iter().map(|old| {
+                                                    let new = group_name.clone();
+                                                    let label = format!("Rename {}", old);
+                                                    html! {
+                                                        <ybc::Control>
+                                                            <a onclick={link.callback(move |_| Msg::RenameRegexGroup(i, j, old.clone(), new.clone()))}><ybc::Button
+                                                                classes={classes!("is-small")}
+                                                                disabled={!step::is_valid_group_name(group_name.trim())}
+                                                            >
+                                                                {label}
+                                                            </ybc::Button></a>
+                                                        </ybc::Control>
+                                                    }
+                                                })}
+                                            </ybc::Field>
+                                            <details class="is-size-7 mb-3">
+                                                <summary>{"Explain this regex"}</summary>
+                                                <p>{explanation}</p>
+                                            </details>
+                                            <ybc::Field>
+                                                <ybc::Checkbox
+                                                    name={format!("step-{}-regex-{}-case-insensitive", i, j)}
+                                                    checked={flags.case_insensitive}
+                                                    update={set_case_insensitive}
+                                                >
+                                                    {" Case insensitive (i)"}
+                                                </ybc::Checkbox>
+                                            </ybc::Field>
+                                            <ybc::Field
+                                                label={"Regex Replacement"}
+                                                label_classes={classes!("is-small")}
+                                                help={
+                                                    if r.replace.is_empty() {
+                                                        "The replacement is empty. This will erase the matched content."
+                                                    } else {
+                                                        ""
+                                                    }
+                                                }
+                                            >
+                                            <ybc::Control
+                                                tag={"div"}
+                                                classes={classes!("has-icons-left")}
+                                            >
+                                            <ybc::Input
+                                                name={format!("step-{}-regex-{}-replacement", i, j)}
+                                                value={r.replace.clone()}
+                                                update={update_regex_replace}
+                                                placeholder={r#"What the matches will be replaced with. Eg. "XYZ"."#}
+                                                size={Small}
+                                                classes={classes!(
+                                                    if r.replace.is_empty() {
+                                                        "is-warning"
+                                                    } else {
+                                                        ""
+                                                    }
+                                                )}
+                                            />
+                                            <span class="icon is-small is-left">
+                                                <i class="fas fa-paste" aria-hidden="true" />
+                                            </span>
+                                            </ybc::Control>
+                                            </ybc::Field>
+                                            if j != 0 {
+                                                <ybc::Field>
+                                                    <ybc::Checkbox
+                                                        name={format!("step-{}-regex-{}-skip-if-prev-no-match", i, j)}
+                                                        checked={r.skip_if_prev_no_match}
+                                                        update={set_skip_if_prev_no_match}
+                                                        classes={classes!("is-size-7")}
+                                                    >
+                                                        {" Only try this regex right after the previous regex made a substitution"}
+                                                    </ybc::Checkbox>
+                                                </ybc::Field>
                                             }
+                                            <ybc::Field>
+                                                <ybc::Checkbox
+                                                    name={format!("step-{}-regex-{}-apply-once-per-line", i, j)}
+                                                    checked={r.apply_once_per_line}
+                                                    update={set_apply_once_per_line}
+                                                    classes={classes!("is-size-7")}
+                                                >
+                                                    {" Apply line by line, skipping the lines already substituted in this step"}
+                                                </ybc::Checkbox>
+                                            </ybc::Field>
+                                            <details class="is-size-7">
+                                                <summary>
+                                                    {"Flags"}
+                                                    if !flags.letters().is_empty() {
+                                                        <code class="ml-1">{flags.letters()}</code>
+                                                    }
+                                                </summary>
+                                                <ybc::Field>
+                                                    <ybc::Checkbox
+                                                        name={format!("step-{}-regex-{}-multiline", i, j)}
+                                                        checked={flags.multiline}
+                                                        update={set_multiline}
+                                                    >
+                                                        {" Multiline (^$ match line boundaries)"}
+                                                    </ybc::Checkbox>
+                                                </ybc::Field>
+                                                <ybc::Field>
+                                                    <ybc::Checkbox
+                                                        name={format!("step-{}-regex-{}-dot-all", i, j)}
+                                                        checked={flags.dot_all}
+                                                        update={set_dot_all}
+                                                    >
+                                                        {" Dot matches newlines (s flag)"}
+                                                    </ybc::Checkbox>
+                                                </ybc::Field>
+                                                <ybc::Field>
+                                                    <div title={"With Unicode, \\b separates any letters, such as \"é\" or \"日\", from non-letters. With ASCII, only a-z, A-Z, 0-9 and _ are word characters, so \\b also splits words at accented letters. This applies to the whole pattern: without Unicode, . and negated classes such as [^a] are rejected, as they could match half of a character, unless written as (?u:.) and (?u:[^a])."}>
+                                                    <ybc::Checkbox
+                                                        name={format!("step-{}-regex-{}-unicode-word-boundary", i, j)}
+                                                        checked={flags.unicode_word_boundary}
+                                                        update={set_unicode_word_boundary}
+                                                    >
+                                                        {" Unicode for the whole pattern, such as in \\b and \\w (u flag)"}
+                                                    </ybc::Checkbox>
+                                                    </div>
+                                                </ybc::Field>
+                                                <ybc::Field>
+                                                    <ybc::Checkbox
+                                                        name={format!("step-{}-regex-{}-verbose", i, j)}
+                                                        checked={flags.verbose}
+                                                        update={set_verbose}
+                                                    >
+                                                        {" Verbose (x flag, whitespace is ignored and # starts a comment)"}
+                                                    </ybc::Checkbox>
+                                                </ybc::Field>
+                                            </details>
+                                            <ybc::Field
+                                                label={"Test Cases"}
+                                                label_classes={classes!("is-small")}
+                                            >
+                                            { for r.test_cases.iter().enumerate().map(|(k, case)| {
+                                                let update_input = link.callback(move |s| Msg::UpdateTestCase(i, j, k, TestCaseField::Input, s));
+                                                let update_expected = link.callback(move |s| Msg::UpdateTestCase(i, j, k, TestCaseField::ExpectedOutput, s));
+                                                html_nested! {
+                                                    <ybc::Field grouped=true>
+                                                        <ybc::Control expanded=true>
+                                                        <ybc::Input
+                                                            name={format!("step-{}-regex-{}-test-{}-input", i, j, k)}
+                                                            value={case.input.clone()}
+                                                            update={update_input}
+                                                            placeholder={"Test input."}
+                                                            size={Small}
+                                                        />
+                                                        </ybc::Control>
+                                                        <ybc::Control expanded=true>
+                                                        <ybc::Input
+                                                            name={format!("step-{}-regex-{}-test-{}-expected", i, j, k)}
+                                                            value={case.expected_output.clone()}
+                                                            update={update_expected}
+                                                            placeholder={"Expected output."}
+                                                            size={Small}
+                                                        />
+                                                        </ybc::Control>
+                                                        <ybc::Control>
+                                                            {match case.passed {
+                                                                Some(true) => html! {<span class="tag is-success">{"pass"}</span>},
+                                                                Some(false) => html! {<span class="tag is-danger">{"fail"}</span>},
+                                                                None => html! {<span class="tag">{"not run"}</span>},
+                                                            }}
+                                                        </ybc::Control>
+                                                    </ybc::Field>
+                                                }
+                                            })}
+                                            <ybc::Field grouped=true>
+                                                <a onclick={add_test_case}><ybc::Button classes={classes!("is-small")}>
+                                                    {"Add Test Case"}
+                                                </ybc::Button></a>
+                                                <a onclick={run_test_cases} class="ml-1"><ybc::Button
+                                                    classes={classes!("is-small")}
+                                                    disabled={r.test_cases.is_empty()}
+                                                >
+                                                    {"Run Test Cases"}
+                                                </ybc::Button></a>
+                                            </ybc::Field>
+                                            </ybc::Field>
+                                            <p>{"(add option to delete the regex)"}</p>
+                                            <p>{"(add option to move up/down the regex)"}</p>
+                                            </>
                                         }
-                                    >
-                                    <ybc::Control
-                                        tag={"div"}
-                                        classes={classes!("has-icons-left")}
-                                    >
-                                    <ybc::Input
-                                        name={format!("step-{}-regex-{}-replacement", i, j)}
-                                        value={r.replace.clone()}
-                                        update={update_regex_replace}
-                                        placeholder={r#"What the matches will be replaced with. Eg. "XYZ"."#}
-                                        size={Small}
-                                        classes={classes!(
-                                            if r.replace.is_empty() {
-                                                "is-warning"
-                                            } else {
-                                                ""
-                                            }
-                                        )}
-                                    />
-                                    <span class="icon is-small is-left">
-                                        <i class="fas fa-paste" />
-                                    </span>
-                                    </ybc::Control>
-                                    </ybc::Field>
-                                    <p>{"(add option to delete the regex)"}</p>
-                                    <p>{"(add option to move up/down the regex)"}</p>
-                                </ybc::Tile>
+                                    </ybc::Tile>
+                                </div>
+                                </>
                             }
                         })}
                         </ybc::Tile>
 
                                 <a onclick={add_regex}><ybc::Button>
                                     <span class="icon is-small">
-                                        <i class="fas fa-hand-point-up"></i>
+                                        <i class="fas fa-hand-point-up" aria-hidden="true"></i>
                                     </span>
                                     <span>
                                         {"Add Regex"}
                                     </span>
                                 </ybc::Button></a>
 
+                                <a onclick={merge_step} class="ml-1"><ybc::Button
+                                    disabled={i + 1 == total_steps}
+                                >
+                                    <span class="icon is-small">
+                                        <i class="fas fa-compress-alt" aria-hidden="true"></i>
+                                    </span>
+                                    <span>
+                                        {"Merge with next step"}
+                                    </span>
+                                </ybc::Button></a>
+
+                                <ybc::Field grouped=true classes={classes!("mt-2")}>
+                                <div class="file">
+                                    <label class="file-label">
+                                        <input
+                                            class="file-input"
+                                            type="file"
+                                            accept=".txt,text/plain"
+                                            onchange={import_regexes}
+                                        />
+                                        <span class="file-cta">
+                                            <span class="file-icon">
+                                                <i class="fas fa-upload" aria-hidden="true"></i>
+                                            </span>
+                                            <span class="file-label">
+                                                {"Import Regexes"}
+                                            </span>
+                                        </span>
+                                    </label>
+                                </div>
+                                <a onclick={paste_regexes} class="ml-1"><ybc::Button>
+                                    <span class="icon is-small">
+                                        <i class="fas fa-paste" aria-hidden="true"></i>
+                                    </span>
+                                    <span>
+                                        {"Paste Regexes"}
+                                    </span>
+                                </ybc::Button></a>
+                                <a onclick={paste_json_regexes} class="ml-1"><ybc::Button>
+                                    <span class="icon is-small">
+                                        <i class="fas fa-paste" aria-hidden="true"></i>
+                                    </span>
+                                    <span>
+                                        {"Paste JSON"}
+                                    </span>
+                                </ybc::Button></a>
+                                <a onclick={export_regexes} class="ml-1"><ybc::Button
+                                    disabled={step.regexes.is_empty()}
+                                >
+                                    <span class="icon is-small">
+                                        <i class="fas fa-file-download" aria-hidden="true"></i>
+                                    </span>
+                                    <span>
+                                        {"Export Regexes"}
+                                    </span>
+                                </ybc::Button></a>
+                                <a onclick={export_tool_json} class="ml-1"><ybc::Button
+                                    disabled={step.regexes.is_empty()}
+                                >
+                                    <span class="icon is-small">
+                                        <i class="fas fa-file-code" aria-hidden="true"></i>
+                                    </span>
+                                    <span title="A JSON array of pattern, replacement and flags, in JavaScript syntax, as used by other regex tools. Inline flags such as (?i) are left as they are.">
+                                        {"Export as JSON"}
+                                    </span>
+                                </ybc::Button></a>
+                                </ybc::Field>
+
+                                if let Some(max) = self.settings.max_regexes_per_step {
+                                    <p class="is-size-7">
+                                        {format!("{} of {} regexes", total_regexes, max)}
+                                    </p>
+                                    <progress
+                                        class={classes!("progress", "is-small", if total_regexes >= max { "is-warning" } else { "is-info" })}
+                                        value={total_regexes.to_string()}
+                                        max={max.to_string()}
+                                    >
+                                        {format!("{} of {} regexes", total_regexes, max)}
+                                    </progress>
+                                }
+
                         </ybc::MessageBody>
                     </ybc::Message>
                     </ybc::Column>
@@ -725,10 +5201,81 @@ impl Component for Model {
             }
         };
 
+        let split_clipboard = {
+            let delimiter = self.split_delimiter.clone();
+            html! {
+                <li class="ml-4">
+                    <ybc::Field addons=true>
+                        <ybc::Control>
+                            <ybc::Input
+                                name="split_delimiter"
+                                value={self.split_delimiter.clone()}
+                                update={link.callback(Msg::UpdateSplitDelimiter)}
+                                size={ybc::Size::Small}
+                                placeholder="Delimiter"
+                            />
+                        </ybc::Control>
+                        <ybc::Control>
+                            <a onclick={link.callback(move |_| {
+                                Msg::SplitClipboardIntoProjects(delimiter.clone())
+                            })}><ybc::Button classes={classes!("is-small")}>
+                                {"Split clipboard"}
+                            </ybc::Button></a>
+                        </ybc::Control>
+                    </ybc::Field>
+                </li>
+            }
+        };
+        let tag_filter = {
+            let mut tags: Vec<&String> = self
+                .text_projects
+                .iter()
+                .flat_map(|p| p.props.tags.iter())
+                .collect();
+            tags.sort();
+            tags.dedup();
+            let tags: Vec<String> = tags.into_iter().cloned().collect();
+            let update = link.callback({
+                let tags = tags.clone();
+                move |value: String| {
+                    Msg::FilterTabsByTag(
+                        value.parse().ok().and_then(|k: usize| tags.get(k).cloned()),
+                    )
+                }
+            });
+            if tags.is_empty() {
+                html! {}
+            } else {
+                html! {
+                    <li class="ml-4">
+                        <ybc::Select
+                            name={"tag-filter"}
+                            value={self.tag_filter.as_ref()
+                                .and_then(|tag| tags.iter().position(|t| t == tag))
+                                .map(|k| k.to_string())
+                                .unwrap_or_default()}
+                            update={update}
+                            size={ybc::Size::Small}
+                        >
+                            <option value="" selected={self.tag_filter.is_none()}>{"All tags"}</option>
+                            { for tags.iter().enumerate().map(|(k, tag)| html! {
+                                <option value={k.to_string()} selected={self.tag_filter.as_ref() == Some(tag)}>
+                                    {tag}
+                                </option>
+                            })}
+                        </ybc::Select>
+                    </li>
+                }
+            }
+        };
         let tabs = if let Some(active_text_project) = active_text_project_index {
             html_nested! {
                 <ybc::Tabs boxed=true>
-                    {for self.text_projects.iter().enumerate().map(|(i, t)| {
+                    {for self.text_projects.iter().enumerate().filter(|(i, t)| {
+                        // the active project is kept, so that it stays reachable
+                        *i == active_text_project
+                            || self.tag_filter.as_ref().map_or(true, |tag| t.props.tags.contains(tag))
+                    }).map(|(i, t)| {
                         let active = i == active_text_project;
                         let title = &t.props.title;
                         let title = if title.trim().is_empty() {
@@ -742,9 +5289,12 @@ impl Component for Model {
                                 if active {"is-active"} else {""},
                                 if i == 0 {"ml-6"} else {""}
                             )}>
-                                <a onclick={
-                                    link.callback(move |_| Msg::SelectTextProject(i))
-                                }>{&title}</a>
+                                <a
+                                    onclick={link.callback(move |_| Msg::SelectTextProject(i))}
+                                    aria-current={active.then(|| "page")}
+                                >{&title}{for t.props.tags.iter().map(|tag| html! {
+                                    <span class={classes!("tag", "is-rounded", "ml-1", tag_color(tag))}>{tag}</span>
+                                })}</a>
                             </li>
                         }
                     })}
@@ -753,6 +5303,8 @@ impl Component for Model {
                             {"+"}
                         </a>
                     </li>
+                    {split_clipboard.clone()}
+                    {tag_filter}
                 </ybc::Tabs>
             }
         } else {
@@ -763,6 +5315,7 @@ impl Component for Model {
                             {"+"}
                         </a>
                     </li>
+                    {split_clipboard.clone()}
                 </ybc::Tabs>
             }
         };
@@ -772,33 +5325,369 @@ impl Component for Model {
             let active_text_project = &self.text_projects[active_text_project_index];
             let update_project_title =
                 link.callback(move |t| Msg::UpdateTextProjectTitle(active_text_project_index, t));
+            let toggle_variables = link.callback({
+                let open = self.project_variables_open;
+                move |_| Msg::ShowProjectVariables(!open)
+            });
             html_nested! {
-                <ybc::Tile ctx={Child}><ybc::Field
+                <ybc::Tile ctx={Child} vertical=true><ybc::Field
                     label={"Text Project Title"}
-                ><ybc::Input
+                    addons=true
+                ><ybc::Control expanded=true><ybc::Input
                     name={format!("project-title-{}", active_text_project_index)}
                     value={active_text_project.props.title.clone()}
                     update={update_project_title}
                     placeholder={r#"The project title. Eg. "Ch015 Google Translate". Defaults to "New Project"."#}
-                /></ybc::Field></ybc::Tile>
+                /></ybc::Control><ybc::Control>
+                    <a onclick={toggle_variables}><ybc::Button
+                        classes={classes!(self.project_variables_open.then(|| "is-info"))}
+                    >
+                        <span class="icon is-small" title="Project variables">
+                            <i class="fas fa-cog" aria-hidden="true"></i>
+                        </span>
+                        <span class="is-sr-only">{"Project variables"}</span>
+                    </ybc::Button></a>
+                </ybc::Control><ybc::Control>
+                    <a onclick={link.callback(move |_| Msg::CloneProject(active_text_project_index))}><ybc::Button
+                        disabled={self.replacement_in_progress}
+                    >
+                        <span class="icon is-small" title="Clone for comparison: the clone is replaced while this project keeps the original text">
+                            <i class="fas fa-clone" aria-hidden="true"></i>
+                        </span>
+                        <span class="is-sr-only">{"Clone for comparison"}</span>
+                    </ybc::Button></a>
+                </ybc::Control><ybc::Control>
+                    <a onclick={link.callback(move |_| Msg::GeneratePrintView(active_text_project_index))}><ybc::Button>
+                        <span class="icon is-small" title="Print view">
+                            <i class="fas fa-print" aria-hidden="true"></i>
+                        </span>
+                        <span class="is-sr-only">{"Print view"}</span>
+                    </ybc::Button></a>
+                </ybc::Control><ybc::Control>
+                    <a onclick={link.callback(move |_| Msg::ExportProjectAsHtml(active_text_project_index))}><ybc::Button>
+                        <span class="icon is-small" title="Download as an HTML report">
+                            <i class="fas fa-file-code" aria-hidden="true"></i>
+                        </span>
+                        <span class="is-sr-only">{"Download as an HTML report"}</span>
+                    </ybc::Button></a>
+                </ybc::Control><ybc::Control>
+                    <a onclick={link.callback(move |_| Msg::ToggleRtlMode(active_text_project_index))}><ybc::Button
+                        classes={classes!(active_text_project.rtl_mode.then(|| "is-info"))}
+                    >
+                        <span title="Switch the text direction between right-to-left and left-to-right">
+                            {if active_text_project.rtl_mode { "RTL" } else { "LTR" }}
+                        </span>
+                    </ybc::Button></a>
+                </ybc::Control></ybc::Field>
+                if self.project_variables_open {
+                    <ybc::Box>
+                        <ybc::Field
+                            label={"Webhook URL"}
+                            label_classes={classes!("is-small")}
+                            help={"The result is POSTed to this URL after each finished replacement."}
+                            addons=true
+                        >
+                            <ybc::Control expanded=true>
+                                <ybc::Input
+                                    name={format!("project-{}-webhook-url", active_text_project_index)}
+                                    value={active_text_project.props.webhook_url.clone().unwrap_or_default()}
+                                    update={link.callback(move |url| Msg::UpdateWebhookUrl(active_text_project_index, url))}
+                                    placeholder={"https://example.com/hook"}
+                                    size={ybc::Size::Small}
+                                />
+                            </ybc::Control>
+                            <ybc::Control>
+                                <a onclick={link.callback(move |_| Msg::SendOutputToWebhook(active_text_project_index))}><ybc::Button
+                                    classes={classes!("is-small")}
+                                    disabled={active_text_project.props.webhook_url.is_none()}
+                                >
+                                    {"Send now"}
+                                </ybc::Button></a>
+                            </ybc::Control>
+                        </ybc::Field>
+                        <ybc::Field
+                            label={"Tags"}
+                            label_classes={classes!("is-small")}
+                            help={"The project tabs can be filtered by tag."}
+                        >
+                            <div class="tags mb-1">
+                                {for active_text_project.props.tags.iter().map(|tag| {
+                                    let remove = {
+                                        let tag = tag.clone();
+                                        link.callback(move |_| Msg::RemoveProjectTag(active_text_project_index, tag.clone()))
+                                    };
+                                    html! {
+                                        <span class={classes!("tag", tag_color(tag))}>
+                                            {tag}
+                                            <ybc::Delete tag={"button"} classes={classes!("is-small")} onclick={remove} />
+                                        </span>
+                                    }
+                                })}
+                            </div>
+                            <ybc::Field addons=true>
+                                <ybc::Control expanded=true>
+                                    <ybc::Input
+                                        name={format!("project-{}-new-tag", active_text_project_index)}
+                                        value={self.new_tag.clone()}
+                                        update={link.callback(Msg::UpdateNewTag)}
+                                        placeholder={"New tag"}
+                                        size={ybc::Size::Small}
+                                    />
+                                </ybc::Control>
+                                <ybc::Control>
+                                    <a onclick={link.callback({
+                                        let tag = self.new_tag.clone();
+                                        move |_| Msg::AddProjectTag(active_text_project_index, tag.clone())
+                                    })}><ybc::Button
+                                        classes={classes!("is-small")}
+                                        disabled={self.new_tag.trim().is_empty()}
+                                    >
+                                        {"Add tag"}
+                                    </ybc::Button></a>
+                                </ybc::Control>
+                            </ybc::Field>
+                        </ybc::Field>
+                        <p class="is-size-7 mb-3">
+                            {"Variables of this project, which take precedence over the global variables of the same name."}
+                        </p>
+                        {self.render_variables_editor(ctx, Some(active_text_project_index))}
+                    </ybc::Box>
+                }
+                </ybc::Tile>
             }
         } else {
             html_nested! {<ybc::Tile ctx={Child}></ybc::Tile>}
         };
 
-        let input = if let Some(active_text_project_index) = active_text_project_index {
+        let input = if let (Some(active_text_project_index), true) =
+            (active_text_project_index, self.input_collapsed)
+        {
+            let characters = self.text_projects[active_text_project_index]
+                .input
+                .chars()
+                .count();
+            html_nested! {
+                <ybc::Tile ctx={Child}><ybc::Field
+                    label={"Original Text"}
+                ><ybc::Field grouped=true>
+                    <ybc::Control>
+                        <span class="is-size-7">{format!("Hidden, with {} characters.", characters)}</span>
+                    </ybc::Control>
+                    <ybc::Control>
+                        <a onclick={link.callback(|_| Msg::ToggleInputCollapsed)}><ybc::Button
+                            classes={classes!("is-small")}
+                        >
+                            <span class="icon is-small">
+                                <i class="fas fa-eye" aria-hidden="true"></i>
+                            </span>
+                            <span>{"Show"}</span>
+                        </ybc::Button></a>
+                    </ybc::Control>
+                </ybc::Field></ybc::Field></ybc::Tile>
+            }
+        } else if let Some(active_text_project_index) = active_text_project_index {
             let active_text_project = &self.text_projects[active_text_project_index];
+            let input_toolbar = if self.input_toolbar_open {
+                let find = self.input_find.clone();
+                let replace = self.input_replace.clone();
+                let occurrences = if find.is_empty() {
+                    0
+                } else {
+                    active_text_project.input.matches(find.as_str()).count()
+                };
+                let (find_, replace_) = (find.clone(), replace.clone());
+                let replace_one =
+                    link.callback(move |_| Msg::ReplaceInInput(find_.clone(), replace_.clone()));
+                let replace_all =
+                    link.callback(move |_| Msg::ReplaceAllInInput(find.clone(), replace.clone()));
+                html! {
+                    <div class="box p-2 input-toolbar">
+                        <ybc::Field grouped=true>
+                            <ybc::Control>
+                            <ybc::Input
+                                name={"input-find"}
+                                value={self.input_find.clone()}
+                                update={link.callback(Msg::FindInInput)}
+                                placeholder={"Find"}
+                                size={ybc::Size::Small}
+                            />
+                            </ybc::Control>
+                            <ybc::Control>
+                            <ybc::Input
+                                name={"input-replace"}
+                                value={self.input_replace.clone()}
+                                update={link.callback(Msg::UpdateInputReplaceText)}
+                                placeholder={"Replace"}
+                                size={ybc::Size::Small}
+                            />
+                            </ybc::Control>
+                            <ybc::Control>
+                                <a onclick={replace_one}><ybc::Button
+                                    classes={classes!("is-small")}
+                                    disabled={occurrences == 0}
+                                >
+                                    {"Replace"}
+                                </ybc::Button></a>
+                            </ybc::Control>
+                            <ybc::Control>
+                                <a onclick={replace_all}><ybc::Button
+                                    classes={classes!("is-small")}
+                                    disabled={occurrences == 0}
+                                >
+                                    {"Replace All"}
+                                </ybc::Button></a>
+                            </ybc::Control>
+                            <ybc::Control>
+                                <span class="is-size-7">{format!("{} found", occurrences)}</span>
+                            </ybc::Control>
+                            <ybc::Control>
+                                <ybc::Delete
+                                    tag={"button"}
+                                    onclick={link.callback(|_| Msg::ShowInputToolbar(false))}
+                                />
+                            </ybc::Control>
+                        </ybc::Field>
+                    </div>
+                }
+            } else {
+                html! {}
+            };
+            let direction = if active_text_project.rtl_mode {
+                "rtl"
+            } else {
+                "ltr"
+            };
+            let input_highlight =
+                if self.highlighted_regex.is_some() && !self.highlighted_ranges.is_empty() {
+                    let input = &active_text_project.input;
+                    let mut segments = vec![];
+                    let mut last = 0;
+                    for (start, end) in self.highlighted_ranges.iter().cloned() {
+                        segments.push(html! {{&input[last..start]}});
+                        segments.push(html! {<mark>{&input[start..end]}</mark>});
+                        last = end;
+                    }
+                    segments.push(html! {{&input[last..]}});
+                    html! {
+                        <div id="input-highlight" class="input-highlight" aria-hidden="true">
+                            { for segments }
+                        </div>
+                    }
+                } else {
+                    html! {}
+                };
+            let show_toolbar = link.batch_callback({
+                let open = self.input_toolbar_open;
+                move |_| (!open).then(|| Msg::ShowInputToolbar(true))
+            });
+            let indent_input = link.batch_callback({
+                let tab_indent = self.settings.tab_indent;
+                move |e: KeyboardEvent| {
+                    use wasm_bindgen::JsCast;
+                    if !tab_indent || e.key() != "Tab" || e.shift_key() {
+                        return None;
+                    }
+                    // the toolbar inputs are also inside of the wrapper
+                    let textarea: web_sys::HtmlTextAreaElement = e.target()?.dyn_into().ok()?;
+                    e.prevent_default();
+                    let start = textarea.selection_start().ok()??;
+                    let end = textarea.selection_end().ok()??;
+                    // the caret is placed after the indent, in UTF-16 units
+                    let caret = start + settings::INDENT.len() as u32;
+                    let indented = textarea
+                        .set_range_text_with_start_and_end(settings::INDENT, start, end)
+                        .and_then(|_| textarea.set_selection_range(caret, caret));
+                    if let Err(err) = indented {
+                        log::error!("Failed to indent the input: {:?}", err);
+                        return None;
+                    }
+                    Some(Msg::InputUpdated(
+                        active_text_project_index,
+                        textarea.value(),
+                    ))
+                }
+            });
+            let input_url = active_text_project.input_url.clone();
+            let load_input = link.batch_callback(move |_| {
+                (!input_url.trim().is_empty()).then(|| {
+                    Msg::LoadInputFromUrl(active_text_project_index, input_url.trim().into())
+                })
+            });
+            let loading = matches!(
+                active_text_project.input_fetch_status,
+                Some(InputFetchStatus::Loading)
+            );
+            let fetch_error = match &active_text_project.input_fetch_status {
+                Some(InputFetchStatus::Failed(err)) => {
+                    Some(format!("Failed to load the text from the URL: {}", err))
+                }
+                _ => None,
+            };
             html_nested! {
                 <ybc::Tile ctx={Child}><ybc::Field
                     label={"Original Text"}
-                    help={"Help message"}
-                ><ybc::TextArea
+                    help={fetch_error.clone().unwrap_or_else(|| "Help message".into())}
+                    help_has_error={fetch_error.is_some()}
+                >
+                <ybc::Field addons=true>
+                    <ybc::Control expanded=true>
+                    <ybc::Input
+                        name={"input-url"}
+                        value={active_text_project.input_url.clone()}
+                        update={link.callback(move |url| Msg::UpdateInputUrl(active_text_project_index, url))}
+                        placeholder={"Or load the original text from a URL"}
+                        size={ybc::Size::Small}
+                    />
+                    </ybc::Control>
+                    <ybc::Control>
+                    <a onclick={load_input}><ybc::Button
+                        classes={classes!("is-small")}
+                        loading={loading}
+                        disabled={active_text_project.input_url.trim().is_empty()}
+                    >
+                        {"Load"}
+                    </ybc::Button></a>
+                    </ybc::Control>
+                </ybc::Field>
+                <div class="input-with-toolbar" onfocusin={show_toolbar} onkeydown={indent_input}>
+                {input_toolbar}
+                <div class="line-numbered" dir={direction}>
+                if !active_text_project.wrap_input {
+                    {render_line_numbers(&active_text_project.input, self.input_scroll_top, self.settings.font_size)}
+                }
+                <ybc::TextArea
                     name={"original-text"}
                     value={active_text_project.input.clone()}
                     update={link.callback(move |value: String| Msg::InputUpdated(active_text_project_index, value.clone()))}
                     placeholder={"Add the original text here.."}
                     rows=6
-                /></ybc::Field></ybc::Tile>
+                    classes={classes!(
+                        self.settings.font_size.class(),
+                        (!active_text_project.wrap_input).then(|| "no-wrap")
+                    )}
+                    loading={loading}
+                    readonly={loading}
+                />
+                </div>
+                {input_highlight}
+                </div>
+                <a onclick={link.callback(|_| Msg::ToggleInputCollapsed)}><ybc::Button
+                    classes={classes!("is-small", "is-text")}
+                >
+                    <span class="icon is-small">
+                        <i class="fas fa-eye-slash" aria-hidden="true"></i>
+                    </span>
+                    <span>{"Hide"}</span>
+                </ybc::Button></a>
+                <a onclick={link.callback(move |_| Msg::ToggleInputWrap(active_text_project_index))}><ybc::Button
+                    classes={classes!("is-small", "is-text")}
+                >
+                    <span class="icon is-small">
+                        <i class={classes!("fas", if active_text_project.wrap_input { "fa-align-left" } else { "fa-ellipsis-h" })} aria-hidden="true"></i>
+                    </span>
+                    <span>{if active_text_project.wrap_input { "Wrap lines" } else { "Don't wrap lines" }}</span>
+                </ybc::Button></a>
+                </ybc::Field></ybc::Tile>
             }
         } else {
             html_nested! {<ybc::Tile ctx={Child}></ybc::Tile>}
@@ -808,20 +5697,94 @@ impl Component for Model {
             let active_text_project = &self.text_projects[active_text_project_index];
             let status = &active_text_project.output_status;
 
+            let direction = if active_text_project.rtl_mode {
+                "rtl"
+            } else {
+                "ltr"
+            };
             let help = match status {
-                OutputStatus::Outdated => "This contains an outdated result.",
-                OutputStatus::InProgress => {
-                    "This contains an outdated result. A new result is being produced.."
+                OutputStatus::Outdated => "This contains an outdated result.".to_string(),
+                OutputStatus::InProgress {
+                    processed,
+                    total,
+                    completed_steps,
+                    total_steps,
+                    fired_regex,
+                } => {
+                    let progress = if *total_steps == 0 {
+                        "".to_string()
+                    } else {
+                        format!(
+                            " Step {} of {} running…",
+                            (completed_steps + 1).min(*total_steps),
+                            total_steps
+                        )
+                    };
+                    let progress = match fired_regex {
+                        Some(regex_index) => {
+                            format!("{} Regex {} just fired.", progress, regex_index + 1)
+                        }
+                        None => progress,
+                    };
+                    let progress = if self.pause_signal.load(Ordering::SeqCst) {
+                        format!("{} Paused, showing the text replaced so far.", progress)
+                    } else {
+                        progress
+                    };
+                    let progress = match self.replacement_eta {
+                        Some(Eta::AtMost(seconds)) => format!(
+                            "{} Estimated: at most ~{:.0}s remaining.",
+                            progress, seconds
+                        ),
+                        Some(Eta::About(seconds)) => {
+                            format!("{} Estimated: ~{:.0}s remaining.", progress, seconds)
+                        }
+                        None => progress,
+                    };
+                    if *total == 1 {
+                        format!(
+                            "This contains an outdated result. A new result is being produced..{}",
+                            progress
+                        )
+                    } else {
+                        format!(
+                            "This contains a partial result. Processed {} of {} chunks.{}",
+                            processed, total, progress
+                        )
+                    }
                 }
-                OutputStatus::Done => "This contains the latest replacement.",
+                OutputStatus::Done => match active_text_project.byte_changes {
+                    Some((inserted, deleted)) => format!(
+                        "This contains the latest replacement. Δ +{} / -{} bytes.",
+                        inserted, deleted
+                    ),
+                    None => format!(
+                        "This contains the latest replacement. Δ {:+} bytes.",
+                        active_text_project.output.len() as isize
+                            - active_text_project.input.len() as isize
+                    ),
+                },
                 OutputStatus::Cancelled(CancelMotive::ManuallyCancelled) => {
-                    "This result is incomplete. The replacement was manually cancelled."
+                    "This result is incomplete. The replacement was manually cancelled.".to_string()
                 }
                 OutputStatus::Cancelled(CancelMotive::CycleDetected) => {
-                    "This result is incomplete. Due to a replacement cycle, the replacement was cancelled."
+                    "This result is incomplete. Due to a replacement cycle, the replacement was cancelled.".to_string()
                 }
                 OutputStatus::Cancelled(CancelMotive::HighGrowth) => {
-                    "This result is incomplete. The replacement was cancelled because it was growing too much."
+                    "This result is incomplete. The replacement was cancelled because it was growing too much.".to_string()
+                }
+                OutputStatus::Cancelled(CancelMotive::Timeout) => {
+                    "This result is incomplete. The replacement was cancelled because it took longer than the timeout.".to_string()
+                }
+            };
+            let help = match &active_text_project.webhook_status {
+                None => help,
+                Some(WebhookStatus::Sending) => format!("{} Sending to the webhook..", help),
+                Some(WebhookStatus::Sent(code)) => {
+                    format!("{} Sent to the webhook ({}).", help, code)
+                }
+                Some(WebhookStatus::Failed(err)) => {
+                    format!("{} Failed to send to the webhook: {}.", help, err)
                 }
             };
 
@@ -829,12 +5792,16 @@ impl Component for Model {
                 <ybc::Tile ctx={Child}><ybc::Field
                     label={"Result"}
                     {help}
-                ><ybc::Control
+                ><div class="line-numbered" dir={direction}>
+                if !active_text_project.wrap_output {
+                    {render_line_numbers(&active_text_project.output, self.output_scroll_top, self.settings.font_size)}
+                }
+                <ybc::Control
                     tag={"div"}
                     classes={classes!(
                         match status {
                             OutputStatus::Outdated | OutputStatus::Done | OutputStatus::Cancelled(_) => {"has-icons-right"},
-                            OutputStatus::InProgress => {"is-loading"}
+                            OutputStatus::InProgress { .. } => {"is-loading"}
                         }
                     )}
                 ><ybc::TextArea
@@ -845,9 +5812,11 @@ impl Component for Model {
                     readonly=true
                     rows=6
                     classes={classes!(
+                        self.settings.font_size.class(),
+                        (!active_text_project.wrap_output).then(|| "no-wrap"),
                         match status {
                             OutputStatus::Outdated => {"is-warning"},
-                            OutputStatus::InProgress => {""}
+                            OutputStatus::InProgress { .. } => {""}
                             OutputStatus::Done => {"is-success"},
                             OutputStatus::Cancelled(_) => {"is-danger"}
                         }
@@ -856,13 +5825,71 @@ impl Component for Model {
                 if matches!(status, OutputStatus::Outdated | OutputStatus::Done) {
                     <span class="icon is-small is-right">
                         if matches!(status, OutputStatus::Done) {
-                            <i class="fas fa-check"></i>
+                            <i class="fas fa-check" aria-hidden="true"></i>
                         } else if matches!(status, OutputStatus::Outdated) {
-                            <i class="fas fa-exclamation-triangle"></i>
+                            <i class="fas fa-exclamation-triangle" aria-hidden="true"></i>
                         }
                     </span>
                 }
-                </ybc::Control></ybc::Field></ybc::Tile>
+                </ybc::Control>
+                </div>
+                <a onclick={link.callback(|_| Msg::ToggleOutputSearch)}><ybc::Button classes={classes!("is-small", "mt-1")}>
+                    <span class="icon is-small">
+                        <i class="fas fa-search" aria-hidden="true"></i>
+                    </span>
+                    <span>
+                        {if self.output_search.is_some() {"Close Search"} else {"Search Result"}}
+                    </span>
+                </ybc::Button></a>
+                <a onclick={link.callback(move |_| Msg::ToggleOutputWrap(active_text_project_index))}><ybc::Button
+                    classes={classes!("is-small", "is-text", "mt-1", "ml-1")}
+                >
+                    <span class="icon is-small">
+                        <i class={classes!("fas", if active_text_project.wrap_output { "fa-align-left" } else { "fa-ellipsis-h" })} aria-hidden="true"></i>
+                    </span>
+                    <span>{if active_text_project.wrap_output { "Wrap lines" } else { "Don't wrap lines" }}</span>
+                </ybc::Button></a>
+                <a onclick={link.callback(move |_| Msg::ArchiveOutput(active_text_project_index))}><ybc::Button
+                    classes={classes!("is-small", "is-text", "mt-1", "ml-1")}
+                    disabled={active_text_project.output.is_empty()}
+                >
+                    <span class="icon is-small">
+                        <i class="fas fa-archive" aria-hidden="true"></i>
+                    </span>
+                    <span>{"Archive"}</span>
+                </ybc::Button></a>
+                {self.render_output_search(ctx, &active_text_project.output)}
+                {for active_text_project.archive.iter().rev().map(|archived| {
+                    let archived_at = js_sys::Date::new(&archived.timestamp.into())
+                        .to_locale_time_string("default");
+                    html! {
+                        <details class="mt-2 is-size-7">
+                            <summary>
+                                {format!(
+                                    "Archived at {}, from {} steps",
+                                    String::from(archived_at),
+                                    archived.step_snapshot.len()
+                                )}
+                            </summary>
+                            <p class="mb-1">{archived.step_snapshot.join(" → ")}</p>
+                            <ybc::TextArea
+                                name={"archived-text"}
+                                value={archived.output.clone()}
+                                update={Callback::noop()}
+                                readonly=true
+                                rows=4
+                                classes={classes!(self.settings.font_size.class())}
+                            />
+                        </details>
+                    }
+                })}
+                if !active_text_project.events.is_empty() {
+                    <details class="mt-2 is-size-7">
+                        <summary>{format!("Activity ({} events)", active_text_project.events.len())}</summary>
+                        {active_text_project.render_events()}
+                    </details>
+                }
+                </ybc::Field></ybc::Tile>
             }
         } else {
             html_nested! {<ybc::Tile ctx={Child}></ybc::Tile>}
@@ -873,6 +5900,12 @@ impl Component for Model {
         } else {
             link.callback(move |_| Msg::StartReplacingText(active_text_project_index))
         };
+        let stream_replace_text = link.batch_callback(move |_| {
+            active_text_project_index
+                .map(|i| Msg::StartStreamingReplacement(i, DEFAULT_STREAM_CHUNK_SIZE))
+        });
+        let start_ab_comparison =
+            link.batch_callback(move |_| active_text_project_index.map(Msg::StartABComparison));
         let toggle_replacement = html_nested! {
             <ybc::Tile ctx={Child}><a onclick={toggle_replace_text}><ybc::Button>
                 {
@@ -882,8 +5915,139 @@ impl Component for Model {
                         "Start Replacing Text"
                     }
                 }
-            </ybc::Button></a></ybc::Tile>
+            </ybc::Button></a>
+            if self.replacement_in_progress {
+                if self.pause_signal.load(Ordering::SeqCst) {
+                    <a onclick={link.callback(|_| Msg::ResumeReplacement)} class="ml-1"><ybc::Button>
+                        <span class="icon is-small">
+                            <i class="fas fa-play" aria-hidden="true"></i>
+                        </span>
+                        <span>{"Resume"}</span>
+                    </ybc::Button></a>
+                } else {
+                    <a onclick={link.callback(|_| Msg::PauseReplacement)} class="ml-1"><ybc::Button>
+                        <span class="icon is-small">
+                            <i class="fas fa-pause" aria-hidden="true"></i>
+                        </span>
+                        <span>{"Pause"}</span>
+                    </ybc::Button></a>
+                }
+            } else {
+                <a onclick={stream_replace_text} class="ml-1"><ybc::Button>
+                    {"Stream Replacing Text"}
+                </ybc::Button></a>
+                <a onclick={start_ab_comparison} class="ml-1"><ybc::Button>
+                    {"A/B Compare"}
+                </ybc::Button></a>
+            }
+            if let Some([(completed_a, total_a), (completed_b, total_b)]) = self.ab_progress {
+                <p class="help" aria-live="polite">{format!(
+                    "A/B comparison running: configuration A completed {} of {} steps, configuration B completed {} of {} steps.",
+                    completed_a, total_a, completed_b, total_b
+                )}</p>
+            }
+            </ybc::Tile>
+
+        };
+
+        let ab_comparison = match active_text_project_index
+            .and_then(|i| self.text_projects[i].ab_result.as_ref().map(|r| (i, r)))
+        {
+            Some((i, (a, b))) => html_nested! {
+                <ybc::Tile ctx={Child}><ybc::Field
+                    label={"A/B Comparison"}
+                    help={"Words only in the A result are struck through, and words only in the B result are underlined."}
+                >
+                    <ybc::Delete
+                        tag={"button"}
+                        classes={classes!("is-pulled-right")}
+                        onclick={link.callback(move |_| Msg::CloseABComparison(i))}
+                    />
+                    {diff::render_side_by_side(a, b, "Configuration A", "Configuration B")}
+                </ybc::Field></ybc::Tile>
+            },
+            None => html_nested! {<ybc::Tile ctx={Child}></ybc::Tile>},
+        };
+
+        let statistics = if let Some(active_text_project_index) = active_text_project_index {
+            let active_text_project = &self.text_projects[active_text_project_index];
+            if active_text_project.regex_durations.is_empty() {
+                html_nested! {<ybc::Tile ctx={Child}></ybc::Tile>}
+            } else {
+                html_nested! {
+                    <ybc::Tile ctx={Child}><ybc::Field
+                        label={"Regex Timings"}
+                        help={"Regexes that took more than 80% of their step's time are highlighted."}
+                    >
+                        {active_text_project.render_regex_durations()}
+                    </ybc::Field></ybc::Tile>
+                }
+            }
+        } else {
+            html_nested! {<ybc::Tile ctx={Child}></ybc::Tile>}
+        };
 
+        let execution_log = {
+            let toggle_log = link.callback(|_| Msg::ToggleExecutionLog);
+            html_nested! {
+                <ybc::Tile ctx={Child}>
+                <ybc::Message classes={classes!("is-small")}>
+                    <ybc::MessageHeader>
+                        <a
+                            onclick={toggle_log}
+                            role="button"
+                            aria-expanded={self.execution_log_open.to_string()}
+                        >
+                            <span class="icon is-small">
+                                <i class={classes!("fas", if self.execution_log_open {"fa-angle-down"} else {"fa-angle-right"})} aria-hidden="true"></i>
+                            </span>
+                            {format!(
+                                "Execution Log ({} substitutions)",
+                                self.execution_log.iter().filter(|entry| !entry.skipped).count()
+                            )}
+                        </a>
+                    </ybc::MessageHeader>
+                    if self.execution_log_open {
+                        <ybc::MessageBody>
+                            <table class="table is-narrow is-fullwidth is-size-7">
+                                <thead>
+                                    <tr>
+                                        <th>{"Step"}</th>
+                                        <th>{"Regex"}</th>
+                                        <th>{"Iteration"}</th>
+                                        <th>{"Matched"}</th>
+                                        <th>{"Replacement"}</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                { for self.execution_log.iter().map(|entry| if entry.skipped {
+                                    html! {
+                                        <tr class="has-text-grey">
+                                            <td>{entry.step_index + 1}</td>
+                                            <td colspan="4">
+                                                <span class="tag is-light">{"skipped"}</span>
+                                                {" The previous step didn't change the text."}
+                                            </td>
+                                        </tr>
+                                    }
+                                } else {
+                                    html! {
+                                        <tr>
+                                            <td>{entry.step_index + 1}</td>
+                                            <td>{entry.regex_index + 1}</td>
+                                            <td>{entry.iteration}</td>
+                                            <td><code>{&entry.matched}</code></td>
+                                            <td><code>{&entry.replacement}</code></td>
+                                        </tr>
+                                    }
+                                })}
+                                </tbody>
+                            </table>
+                        </ybc::MessageBody>
+                    }
+                </ybc::Message>
+                </ybc::Tile>
+            }
         };
 
         let body = html_nested! {
@@ -897,6 +6061,24 @@ impl Component for Model {
                             "has-background-info-light"
                         )}
                     >
+                        if !self.settings.welcome_dismissed {
+                            <ybc::Message classes={classes!("is-info")}>
+                                <ybc::MessageHeader>
+                                    <p>{"Welcome"}</p>
+                                    <ybc::Delete
+                                        tag={"button"}
+                                        onclick={link.callback(|_| Msg::DismissWelcome)}
+                                    />
+                                </ybc::MessageHeader>
+                                <ybc::MessageBody>
+                                    {"Each step below holds regexes that are tried against the original text. "}
+                                    {"The first step has a sample regex that replaces "}<code>{"hello"}</code>
+                                    {" with "}<code>{"world"}</code>
+                                    {". Write some text with \"hello\" in it, then press \"Start Replacing Text\"."}
+                                </ybc::MessageBody>
+                            </ybc::Message>
+                        }
+                        {search_results}
                         {edit_steps}
                     </ybc::Tile>
                     <ybc::Tile vertical=true>
@@ -927,15 +6109,122 @@ impl Component for Model {
                             {input}
                             {toggle_replacement}
                             {output}
+                            {ab_comparison}
+                            {statistics}
+                            {execution_log}
                         </ybc::Tile>
                     </ybc::Tile>
                 </ybc::Tile>
             </ybc::Tile>
         };
 
+        let regex_diagram = {
+            let regex = self
+                .regex_diagram
+                .and_then(|(i, j)| self.steps.get(i).and_then(|step| step.regexes.get(j)));
+            match regex {
+                Some(r) => {
+                    let close = link.callback(|_| Msg::CloseRegexDiagram);
+                    let pattern = match &r.r#match {
+                        Ok(re) => re.as_str(),
+                        Err(s) => s.as_str(),
+                    };
+                    let title = if r.title.trim().is_empty() {
+                        "Regex Diagram".to_string()
+                    } else {
+                        r.title.clone()
+                    };
+                    html! {
+                        <div class="modal is-active" role="dialog" aria-modal="true">
+                            <div class="modal-background" onclick={close.clone()}></div>
+                            <div class="modal-card regex-diagram-card">
+                                <header class="modal-card-head">
+                                    <p class="modal-card-title">{title}</p>
+                                    <ybc::Delete tag={"button"} onclick={close} />
+                                </header>
+                                <section class="modal-card-body">
+                                    <p class="mb-3"><code>{pattern}</code></p>
+                                    {match diagram::regex_diagram(pattern, r.flags) {
+                                        Ok(svg) => svg,
+                                        Err(err) => html! {
+                                            <pre class="has-text-danger">{err}</pre>
+                                        },
+                                    }}
+                                </section>
+                            </div>
+                        </div>
+                    }
+                }
+                None => html! {},
+            }
+        };
+
+        let confirmation = if let Some(action) = self.pending_confirmation {
+            let cancel = link.callback(|_| Msg::CancelPendingAction);
+            let confirm = link.callback(|_| Msg::ConfirmPendingAction);
+            html! {
+                <div class="modal is-active" role="dialog" aria-modal="true">
+                    <div class="modal-background" onclick={cancel.clone()}></div>
+                    <div class="modal-card">
+                        <header class="modal-card-head">
+                            <p class="modal-card-title">{"Are you sure?"}</p>
+                            <ybc::Delete tag={"button"} onclick={cancel.clone()} />
+                        </header>
+                        <section class="modal-card-body">
+                            {action.description()}
+                        </section>
+                        <footer class="modal-card-foot">
+                            <div onclick={confirm}><ybc::Button classes={classes!("is-danger")}>
+                                {action.confirm_label()}
+                            </ybc::Button></div>
+                            <div onclick={cancel} class="ml-2"><ybc::Button>
+                                {"Cancel"}
+                            </ybc::Button></div>
+                        </footer>
+                    </div>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+
+        let stats = if self.stats_open {
+            let close = link.callback(|_| Msg::ShowStats(false));
+            html! {
+                <div class="modal is-active" role="dialog" aria-modal="true">
+                    <div class="modal-background" onclick={close.clone()}></div>
+                    <div class="modal-card">
+                        <header class="modal-card-head">
+                            <p class="modal-card-title">{"Replacement Runs"}</p>
+                            <ybc::Delete tag={"button"} onclick={close} />
+                        </header>
+                        <section class="modal-card-body">
+                            {stats::render_run_history(&self.run_history)}
+                        </section>
+                    </div>
+                </div>
+            }
+        } else {
+            html! {}
+        };
+
         html! {<>
         {navbar}
         {body}
+        {regex_diagram}
+        {confirmation}
+        <div class="notifications">
+            { for self.notifications.iter().enumerate().map(|(i, notification)| {
+                notification.render(link.callback(move |_| Msg::DismissNotification(i)))
+            })}
+        </div>
+        {stats}
+        {self.render_variables(ctx)}
+        {self.render_settings(ctx)}
+        {self.render_cheatsheet(ctx)}
+        <div aria-live="polite" aria-atomic="true" class="is-sr-only">
+            {&self.screen_reader_message}
+        </div>
         </>
         }
     }
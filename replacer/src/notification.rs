@@ -0,0 +1,73 @@
+use yew::prelude::*;
+
+/// How long each notification is shown, in milliseconds.
+pub const NOTIFICATION_DURATION_MS: u32 = 4000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationLevel {
+    Info,
+    Warning,
+    Error,
+}
+
+impl NotificationLevel {
+    /// The Bulma color class of the notification.
+    pub fn class(&self) -> &'static str {
+        match self {
+            NotificationLevel::Info => "is-info",
+            NotificationLevel::Warning => "is-warning",
+            NotificationLevel::Error => "is-danger",
+        }
+    }
+}
+
+/// A transient message for the user, shown on a corner of the page.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub message: String,
+    pub level: NotificationLevel,
+    /// When the notification is dismissed, in milliseconds since the epoch.
+    pub expires_at: f64,
+}
+
+impl Notification {
+    /// Creates a notification that expires after
+    /// [`NOTIFICATION_DURATION_MS`].
+    pub fn new(level: NotificationLevel, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            level,
+            expires_at: js_sys::Date::now() + NOTIFICATION_DURATION_MS as f64,
+        }
+    }
+
+    pub fn info(message: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Info, message)
+    }
+
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Warning, message)
+    }
+
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(NotificationLevel::Error, message)
+    }
+
+    /// Writes the notification to the browser console as well.
+    pub fn log(&self) {
+        match self.level {
+            NotificationLevel::Info => log::info!("{}", self.message),
+            NotificationLevel::Warning => log::warn!("{}", self.message),
+            NotificationLevel::Error => log::error!("{}", self.message),
+        }
+    }
+
+    pub fn render(&self, dismiss: Callback<MouseEvent>) -> Html {
+        html! {
+            <ybc::Notification classes={classes!(self.level.class())}>
+                <ybc::Delete tag={"button"} onclick={dismiss} />
+                {&self.message}
+            </ybc::Notification>
+        }
+    }
+}
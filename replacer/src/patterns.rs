@@ -0,0 +1,48 @@
+/// Ready-made regexes, as `(key, description, pattern)`.
+///
+/// The description becomes the title of the regex that the pattern is
+/// inserted into.
+pub const COMMON_PATTERNS: &[(&str, &str, &str)] = &[
+    ("html-tags", "Strip HTML tags", r"<[^>]+>"),
+    ("html-comments", "Strip HTML comments", r"(?s)<!--.*?-->"),
+    ("multiple-spaces", "Collapse repeated spaces", r"[ \t]{2,}"),
+    (
+        "trailing-whitespace",
+        "Trim trailing whitespace",
+        r"(?m)[ \t]+$",
+    ),
+    (
+        "leading-whitespace",
+        "Trim leading whitespace",
+        r"(?m)^[ \t]+",
+    ),
+    ("blank-lines", "Collapse blank lines", r"\n{3,}"),
+    ("url", "Match URLs", r#"https?://[^\s<>"]+"#),
+    (
+        "email",
+        "Match email addresses",
+        r"[\w.+-]+@[\w-]+(?:\.[\w-]+)+",
+    ),
+    ("number", "Match numbers", r"-?\d+(?:[.,]\d+)?"),
+    (
+        "date",
+        "Match ISO dates",
+        r"(?P<year>\d{4})-(?P<month>\d{2})-(?P<day>\d{2})",
+    ),
+    ("quoted", "Match double-quoted text", r#""([^"]*)""#),
+    ("parenthesized", "Match parenthesized text", r"\(([^)]*)\)"),
+    ("non-ascii", "Match non-ASCII characters", r"[^\x00-\x7F]+"),
+    (
+        "cjk",
+        "Match Chinese, Japanese or Korean characters",
+        r"[\p{Han}\p{Hiragana}\p{Katakana}\p{Hangul}]+",
+    ),
+];
+
+/// The pattern of the key, as `(description, pattern)`.
+pub fn find(key: &str) -> Option<(&'static str, &'static str)> {
+    COMMON_PATTERNS
+        .iter()
+        .find(|(k, _, _)| *k == key)
+        .map(|(_, description, pattern)| (*description, *pattern))
+}
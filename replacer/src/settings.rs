@@ -0,0 +1,170 @@
+use gloo_storage::Storage;
+use serde::{Deserialize, Serialize};
+
+/// What Tab inserts into the input text, when indenting is enabled.
+pub const INDENT: &str = "  ";
+
+/// The localStorage key that the settings are persisted under.
+const SETTINGS_KEY: &str = "settings";
+
+/// Preferences that apply to every project and step.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GlobalSettings {
+    /// Whether the replacement runs by itself after the input changes.
+    pub auto_run: bool,
+    /// How long, in milliseconds, a replacement can run before it's
+    /// cancelled. Is `None` if there is no limit.
+    pub global_timeout_ms: Option<u64>,
+    /// How many times longer than the input the text can grow before the
+    /// replacement is cancelled.
+    pub growth_factor: f64,
+    /// How many substitutions a step makes before moving on to the next
+    /// step. Is `None` if there is no limit.
+    pub max_iterations_default: Option<usize>,
+    /// How many regexes each step can have, as large steps slow the page
+    /// down. Is `None` if there is no limit.
+    pub max_regexes_per_step: Option<usize>,
+    pub theme: Theme,
+    /// The font size of the input and output text areas.
+    ///
+    /// Defaults to the smallest, which is Bulma's regular size.
+    pub font_size: FontSize,
+    /// Whether Tab indents the input text, instead of moving the focus away.
+    pub tab_indent: bool,
+    /// Whether colors are darkened for a contrast of at least 7:1, which
+    /// combines with either theme.
+    pub high_contrast: bool,
+    /// Whether the introduction for first-time users, along with its sample
+    /// regex, was dismissed.
+    pub welcome_dismissed: bool,
+}
+
+impl Default for GlobalSettings {
+    fn default() -> Self {
+        Self {
+            auto_run: false,
+            global_timeout_ms: None,
+            growth_factor: 4.0,
+            max_iterations_default: None,
+            max_regexes_per_step: None,
+            theme: Theme::Light,
+            font_size: FontSize::Small,
+            tab_indent: false,
+            high_contrast: false,
+            welcome_dismissed: false,
+        }
+    }
+}
+
+impl GlobalSettings {
+    /// Loads the persisted settings, or the defaults if there are none.
+    pub fn load() -> Self {
+        gloo_storage::LocalStorage::get(SETTINGS_KEY).unwrap_or_default()
+    }
+
+    /// Persists the settings, so they are kept after a reload.
+    pub fn save(&self) {
+        if let Err(err) = gloo_storage::LocalStorage::set(SETTINGS_KEY, self) {
+            log::error!("Failed to save the settings: {}", err);
+        }
+    }
+}
+
+/// The numeric settings as typed, which can be incomplete, such as `1.`,
+/// until their field loses the focus.
+#[derive(Clone, Default)]
+pub struct SettingsDraft {
+    pub global_timeout_ms: String,
+    pub growth_factor: String,
+    pub max_regexes_per_step: String,
+    pub max_iterations_default: String,
+}
+
+impl SettingsDraft {
+    pub fn new(settings: &GlobalSettings) -> Self {
+        let optional = |value: Option<_>| value.map(|v: usize| v.to_string()).unwrap_or_default();
+        Self {
+            global_timeout_ms: settings
+                .global_timeout_ms
+                .map(|t| t.to_string())
+                .unwrap_or_default(),
+            growth_factor: settings.growth_factor.to_string(),
+            max_regexes_per_step: optional(settings.max_regexes_per_step),
+            max_iterations_default: optional(settings.max_iterations_default),
+        }
+    }
+
+    /// Writes the draft over the settings, or explains the first setting
+    /// that is invalid. Empty optional settings have no limit.
+    pub fn apply(&self, settings: &GlobalSettings) -> Result<GlobalSettings, String> {
+        fn optional<T: std::str::FromStr>(value: &str, label: &str) -> Result<Option<T>, String> {
+            match value.trim() {
+                "" => Ok(None),
+                value => value
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| format!("The {} must be a whole number, or empty.", label)),
+            }
+        }
+        let growth_factor = match self.growth_factor.trim().parse::<f64>() {
+            Ok(factor) if factor >= 1.0 => factor,
+            _ => return Err("The growth factor must be a number of at least 1.".into()),
+        };
+        Ok(GlobalSettings {
+            global_timeout_ms: optional(&self.global_timeout_ms, "replacement timeout")?,
+            growth_factor,
+            max_regexes_per_step: optional(&self.max_regexes_per_step, "maximum regexes per step")?,
+            max_iterations_default: optional(
+                &self.max_iterations_default,
+                "maximum iterations per step",
+            )?,
+            ..settings.clone()
+        })
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// The other theme.
+    pub fn toggled(self) -> Self {
+        match self {
+            Theme::Light => Theme::Dark,
+            Theme::Dark => Theme::Light,
+        }
+    }
+
+    /// Every theme, with their labels.
+    pub const OPTIONS: &'static [(Theme, &'static str)] =
+        &[(Theme::Light, "Light"), (Theme::Dark, "Dark")];
+}
+
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FontSize {
+    Small,
+    Medium,
+    Large,
+}
+
+impl FontSize {
+    /// Every font size, with their labels.
+    pub const OPTIONS: &'static [(FontSize, &'static str)] = &[
+        (FontSize::Small, "Small"),
+        (FontSize::Medium, "Medium"),
+        (FontSize::Large, "Large"),
+    ];
+
+    /// The Bulma font size modifier of the text areas.
+    pub fn class(self) -> &'static str {
+        match self {
+            FontSize::Small => "is-size-6",
+            FontSize::Medium => "is-size-5",
+            FontSize::Large => "is-size-4",
+        }
+    }
+}
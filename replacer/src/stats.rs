@@ -0,0 +1,146 @@
+use crate::step::StepProps;
+use crate::{ProjectIndex, RegexIndex, StepRegex};
+use indexmap::IndexMap;
+use yew::prelude::*;
+
+/// How many of the latest replacement runs are kept in the history.
+pub const MAX_RUN_HISTORY: usize = 20;
+
+/// A replacement run that is still in progress.
+pub struct RunInProgress {
+    /// When the run started, in milliseconds since the epoch.
+    pub started_at: f64,
+    /// The position and pattern of each regex of each step that takes part
+    /// in the replacement.
+    pub patterns: Vec<Vec<(RegexIndex, String)>>,
+}
+
+impl RunInProgress {
+    /// Starts tracking a run of these steps regexes.
+    pub fn new(steps_regexes: &[(StepProps, Vec<StepRegex>)]) -> Self {
+        Self {
+            started_at: js_sys::Date::now(),
+            patterns: steps_regexes
+                .iter()
+                .map(|(_, regexes)| {
+                    regexes
+                        .iter()
+                        .map(|r| (r.index, r.re.as_str().into()))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A finished replacement run.
+pub struct RunRecord {
+    /// When the run started, in milliseconds since the epoch.
+    pub timestamp: f64,
+    pub project_index: ProjectIndex,
+    pub steps_used: usize,
+    pub total_matches: usize,
+    /// How long the run took, in milliseconds.
+    pub duration: f64,
+    /// How many characters the input had.
+    pub characters_processed: usize,
+    /// How many matches each regex pattern replaced.
+    pub regex_matches: Vec<(String, usize)>,
+}
+
+impl RunRecord {
+    /// Creates the record of a finished run, with its per-regex match counts
+    /// as returned by the replacement.
+    pub fn new(
+        run: RunInProgress,
+        project_index: ProjectIndex,
+        characters_processed: usize,
+        matches: &[Vec<usize>],
+    ) -> Self {
+        let regex_matches: Vec<(String, usize)> = run
+            .patterns
+            .into_iter()
+            .zip(matches.iter())
+            .flat_map(|(patterns, counts)| {
+                patterns.into_iter().map(move |(index, pattern)| {
+                    (pattern, counts.get(index).cloned().unwrap_or_default())
+                })
+            })
+            .collect();
+        Self {
+            timestamp: run.started_at,
+            project_index,
+            steps_used: matches.len(),
+            total_matches: regex_matches.iter().map(|(_, count)| count).sum(),
+            duration: js_sys::Date::now() - run.started_at,
+            characters_processed,
+            regex_matches,
+        }
+    }
+}
+
+/// Renders the aggregated statistics and the list of the latest runs.
+pub fn render_run_history(history: &[RunRecord]) -> Html {
+    if history.is_empty() {
+        return html! {
+            <p>{"No replacement has finished yet."}</p>
+        };
+    }
+
+    let total_characters: usize = history.iter().map(|r| r.characters_processed).sum();
+    let mut pattern_matches = IndexMap::<&str, usize>::new();
+    for (pattern, count) in history.iter().flat_map(|r| r.regex_matches.iter()) {
+        *pattern_matches.entry(pattern.as_str()).or_default() += count;
+    }
+    let most_used = pattern_matches
+        .into_iter()
+        .filter(|(_, count)| *count > 0)
+        .max_by_key(|(_, count)| *count);
+
+    html! {
+        <>
+        <ybc::Content>
+            <p class="is-size-7">{format!("From the latest {} runs.", history.len())}</p>
+            <ul>
+                <li>{format!("Replacements run: {}", history.len())}</li>
+                <li>{format!("Characters processed: {}", total_characters)}</li>
+                <li>
+                    {"Most used regex: "}
+                    {match most_used {
+                        Some((pattern, count)) => html! {
+                            <><code>{pattern}</code>{format!(" ({} matches)", count)}</>
+                        },
+                        None => html! {"none"},
+                    }}
+                </li>
+            </ul>
+        </ybc::Content>
+        <table class="table is-narrow is-fullwidth is-size-7">
+            <thead>
+                <tr>
+                    <th>{"Started"}</th>
+                    <th>{"Project"}</th>
+                    <th>{"Steps"}</th>
+                    <th>{"Matches"}</th>
+                    <th>{"Duration"}</th>
+                </tr>
+            </thead>
+            <tbody>
+            { for history.iter().rev().map(|r| {
+                let started = js_sys::Date::new(&r.timestamp.into())
+                    .to_locale_time_string("default");
+                html! {
+                    <tr>
+                        <td>{String::from(started)}</td>
+                        <td>{r.project_index + 1}</td>
+                        <td>{r.steps_used}</td>
+                        <td>{r.total_matches}</td>
+                        <td>{format!("{:.0} ms", r.duration)}</td>
+                    </tr>
+                }
+            })}
+            </tbody>
+        </table>
+        </>
+    }
+}
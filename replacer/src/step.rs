@@ -1,14 +1,53 @@
+use crate::{RegexIndex, StepRegex};
+use serde::Deserialize;
 use yew::prelude::*;
 
+#[derive(Clone, Copy, PartialEq)]
 pub enum VirtualSort {
     None,
     CharLength,
     CharLengthRev,
+    AlphaTitle,
+    AlphaTitleRev,
 }
 
+impl VirtualSort {
+    /// Every ordering, with their labels.
+    pub const OPTIONS: &'static [(VirtualSort, &'static str)] = &[
+        (VirtualSort::None, "Declaration order"),
+        (VirtualSort::CharLength, "Longest pattern first"),
+        (VirtualSort::CharLengthRev, "Shortest pattern first"),
+        (VirtualSort::AlphaTitle, "Title A-Z"),
+        (VirtualSort::AlphaTitleRev, "Title Z-A"),
+    ];
+
+    /// Returns the regex indexes in the order that they should be tried.
+    ///
+    /// The sort is stable, so regexes that compare equal keep their
+    /// declaration order.
+    pub fn order(self, regexes: &[StepRegex]) -> Vec<RegexIndex> {
+        let mut indexes: Vec<RegexIndex> = (0..regexes.len()).collect();
+        let len = |i: &RegexIndex| regexes[*i].re.as_str().len();
+        let title = |i: &RegexIndex| regexes[*i].title.to_lowercase();
+        match self {
+            VirtualSort::None => {}
+            VirtualSort::CharLength => indexes.sort_by_key(|i| std::cmp::Reverse(len(i))),
+            VirtualSort::CharLengthRev => indexes.sort_by_key(len),
+            VirtualSort::AlphaTitle => indexes.sort_by_cached_key(title),
+            VirtualSort::AlphaTitleRev => {
+                indexes.sort_by_cached_key(|i| std::cmp::Reverse(title(i)))
+            }
+        }
+        indexes
+    }
+}
+
+#[derive(Clone)]
 pub struct StepProps {
     /// The step title.
     pub title: String,
+    /// What the step is for, as written by the user.
+    pub description: String,
     /// Whether the step is enabled during a replacement run.
     pub enabled: bool,
     /// Whether it is selected for edit.
@@ -17,22 +56,330 @@ pub struct StepProps {
     pub restart_on_match: bool,
     /// In which regex ordering should replacement run on.
     pub virtual_sort: VirtualSort,
+    /// Whether the step is skipped when the previous step didn't change the
+    /// text.
+    pub run_only_if_prev_changed: bool,
+    /// Whether the step ends right after its first substitution, instead of
+    /// looping until no regex matches.
+    pub stop_on_first_match: bool,
+    /// When the step runs, relative to the other steps. Lower priorities run
+    /// first, and steps of the same priority run in declaration order.
+    pub priority: usize,
+    /// How long, in milliseconds, the replacement pauses after each
+    /// substitution of this step, so it can be followed in slow motion. Is
+    /// `None` if it doesn't pause.
+    pub pause_between_regexes_ms: Option<u64>,
+    /// Whether the step is part of the first configuration of an A/B
+    /// comparison.
+    pub in_config_a: bool,
+    /// Whether the step is part of the second configuration of an A/B
+    /// comparison.
+    pub in_config_b: bool,
+}
+
+/// One of the two configurations of an A/B comparison.
+#[derive(Clone, Copy, PartialEq)]
+pub enum AbConfig {
+    A,
+    B,
 }
 
+#[derive(Clone)]
 pub struct RegexInfo {
     pub title: String,
+    /// Whether the regex is used by replacements. Disabled regexes are
+    /// skipped as if they didn't exist.
+    pub enabled: bool,
     pub r#match: Result<regex::Regex, String>,
     pub match_parse_error: Option<regex::Error>,
     pub replace: String,
+    /// Options that the match is compiled with.
+    pub flags: RegexFlags,
+    /// Examples that are checked against this regex alone.
+    pub test_cases: Vec<RegexTestCase>,
+    /// Whether this regex is only tried if the previous regex of the step
+    /// made the latest substitution.
+    pub skip_if_prev_no_match: bool,
+    /// Whether this regex skips the lines that were already substituted by a
+    /// once-per-line regex of the same step.
+    pub apply_once_per_line: bool,
+    /// How many times this regex matches the active project's input.
+    ///
+    /// Is `None` if the regex is not valid.
+    pub match_count: Option<usize>,
+    /// Whether this regex matches the active project's input as-is, without
+    /// any previous replacement.
+    ///
+    /// Is `None` if the regex is not valid or there is no active project.
+    pub has_match_on_input: Option<bool>,
+    /// Average time, in milliseconds, that matching this regex against the
+    /// active project's input took in the latest benchmark.
+    ///
+    /// Is `None` if the regex was not benchmarked since its last change.
+    pub avg_ms: Option<f64>,
+    /// Whether the match time grows super-linearly with the input length.
+    ///
+    /// Is `None` if the regex was not analyzed since its last change.
+    pub complexity_risk: Option<bool>,
+}
+
+/// How many times a regex is matched against the input when benchmarked.
+pub const BENCHMARK_ITERATIONS: usize = 1000;
+
+/// Average match time, in milliseconds, above which a regex is flagged as
+/// slow, as it may be backtracking catastrophically.
+pub const SLOW_MATCH_MS: f64 = 1.0;
+
+/// Options that change how a regex match is compiled.
+#[derive(Clone, Copy, PartialEq)]
+pub struct RegexFlags {
+    /// Whether letters match both their lowercase and uppercase forms.
+    pub case_insensitive: bool,
+    /// Whether `^` and `$` match at the start and end of each line, instead
+    /// of only at the start and end of the whole text.
+    pub multiline: bool,
+    /// Whether `.` also matches `\n`.
+    ///
+    /// Independent from `multiline`, which only changes the anchors.
+    pub dot_all: bool,
+    /// Whether `\b`, `\w` and the other classes follow Unicode, instead of
+    /// only ASCII.
+    ///
+    /// This applies to the whole pattern, so without it `.` and negated
+    /// classes are rejected unless they opt back in with `(?u:...)`.
+    pub unicode_word_boundary: bool,
+    /// Whether whitespace in the pattern is ignored and `#` starts a comment
+    /// until the end of the line, so patterns can be written over many lines.
+    pub verbose: bool,
+}
+
+impl Default for RegexFlags {
+    fn default() -> Self {
+        Self {
+            case_insensitive: false,
+            multiline: false,
+            dot_all: false,
+            unicode_word_boundary: true,
+            verbose: false,
+        }
+    }
+}
+
+impl RegexFlags {
+    /// Compiles the pattern with these flags.
+    pub fn build(self, pattern: &str) -> Result<regex::Regex, regex::Error> {
+        regex::RegexBuilder::new(pattern)
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multiline)
+            .dot_matches_new_line(self.dot_all)
+            .unicode(self.unicode_word_boundary)
+            .ignore_whitespace(self.verbose)
+            .build()
+    }
+
+    /// A parser that reads patterns as [`RegexFlags::build`] compiles them, so
+    /// analyses of the pattern take these flags into account.
+    pub fn parser(self) -> regex_syntax::Parser {
+        regex_syntax::ParserBuilder::new()
+            .case_insensitive(self.case_insensitive)
+            .multi_line(self.multiline)
+            .dot_matches_new_line(self.dot_all)
+            .unicode(self.unicode_word_boundary)
+            .ignore_whitespace(self.verbose)
+            .build()
+    }
+
+    /// The inline flag letters of the flags that differ from the defaults.
+    ///
+    /// Disabled flags are preceded by `-`, as in `(?m-u)`.
+    pub fn letters(self) -> String {
+        let mut letters = String::new();
+        if self.case_insensitive {
+            letters.push('i');
+        }
+        if self.multiline {
+            letters.push('m');
+        }
+        if self.dot_all {
+            letters.push('s');
+        }
+        if self.verbose {
+            letters.push('x');
+        }
+        if !self.unicode_word_boundary {
+            letters.push_str("-u");
+        }
+        letters
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct RegexTestCase {
+    pub input: String,
+    pub expected_output: String,
+    /// Whether the latest run produced the expected output.
+    ///
+    /// Is `None` if the case was not yet run since its last change.
+    pub passed: Option<bool>,
+}
+
+pub enum TestCaseField {
+    Input,
+    ExpectedOutput,
+}
+
+impl RegexInfo {
+    pub fn new(title: String, search: String, replace: String) -> Self {
+        let mut info = Self {
+            title,
+            replace,
+            ..Default::default()
+        };
+        info.set_match(search);
+        info
+    }
+
+    /// Compiles and sets the regex match, keeping the parse error if any.
+    pub fn set_match(&mut self, search: String) {
+        self.avg_ms = None;
+        self.complexity_risk = None;
+        match self.flags.build(&search) {
+            Ok(re) => {
+                self.r#match = Ok(re);
+                self.match_parse_error = None;
+            }
+            Err(err) => {
+                self.r#match = Err(search);
+                self.match_parse_error = Some(err);
+            }
+        }
+    }
+
+    /// Sets the flags and recompiles the current match with them.
+    pub fn set_flags(&mut self, flags: RegexFlags) {
+        self.flags = flags;
+        let search = match &self.r#match {
+            Ok(re) => re.as_str().to_string(),
+            Err(search) => search.clone(),
+        };
+        self.set_match(search);
+    }
+
+    /// Names of the named capture groups of the match, in order.
+    pub fn group_names(&self) -> Vec<String> {
+        match &self.r#match {
+            Ok(re) => re.capture_names().flatten().map(String::from).collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Appends an empty named group to the match and a reference to it to
+    /// the replacement.
+    pub fn add_named_group(&mut self, name: &str) {
+        let search = match &self.r#match {
+            Ok(re) => format!("{}(?P<{}>)", re.as_str(), name),
+            Err(search) => format!("{}(?P<{}>)", search, name),
+        };
+        self.replace.push_str(&format!("${{{}}}", name));
+        self.set_match(search);
+    }
+
+    /// Renames the named group in both the match and the replacement.
+    pub fn rename_group(&mut self, old: &str, new: &str) {
+        let search = match &self.r#match {
+            Ok(re) => re.as_str().to_string(),
+            Err(search) => search.clone(),
+        };
+        let search = search.replace(&format!("(?P<{}>", old), &format!("(?P<{}>", new));
+        self.replace = rename_group_references(&self.replace, old, new);
+        self.set_match(search);
+    }
+
+    /// Times how long, on average, matching this regex against the input
+    /// takes, over `BENCHMARK_ITERATIONS` matches.
+    pub fn benchmark(&mut self, input: &str) {
+        let re = match &self.r#match {
+            Ok(re) => re,
+            Err(_) => {
+                log::warn!("Can't benchmark a regex that has no valid match.");
+                self.avg_ms = None;
+                return;
+            }
+        };
+        let start = js_sys::Date::now();
+        for _ in 0..BENCHMARK_ITERATIONS {
+            re.is_match(input);
+        }
+        let elapsed = js_sys::Date::now() - start;
+        self.avg_ms = Some(elapsed / BENCHMARK_ITERATIONS as f64);
+    }
+
+    /// Checks whether this regex may be vulnerable to catastrophic
+    /// backtracking.
+    pub fn analyze_complexity(&mut self) {
+        let re = match &self.r#match {
+            Ok(re) => re,
+            Err(_) => {
+                log::warn!("Can't analyze a regex that has no valid match.");
+                self.complexity_risk = None;
+                return;
+            }
+        };
+        self.complexity_risk = crate::complexity::has_redos_risk(re, self.flags);
+        if self.complexity_risk.is_none() {
+            log::warn!("Couldn't generate an input that matches the start of the regex.");
+        }
+    }
+
+    /// Whether every test case passed in their latest run.
+    ///
+    /// Is `None` if there are no test cases, or some were not run since
+    /// their last change.
+    pub fn test_cases_passed(&self) -> Option<bool> {
+        if self.test_cases.is_empty() {
+            return None;
+        }
+        self.test_cases
+            .iter()
+            .try_fold(true, |all, case| case.passed.map(|passed| all && passed))
+    }
+
+    /// Applies this regex replacement, isolated from any step context, to
+    /// each test case input and compares it against the expected output.
+    pub fn run_test_cases(&mut self) {
+        let re = match &self.r#match {
+            Ok(re) => re,
+            Err(_) => {
+                log::warn!("Test cases can't run while the regex has no valid match.");
+                for case in self.test_cases.iter_mut() {
+                    case.passed = None;
+                }
+                return;
+            }
+        };
+        for case in self.test_cases.iter_mut() {
+            let output = re.replace_all(&case.input, &self.replace);
+            case.passed = Some(output == case.expected_output);
+        }
+    }
 }
 
 impl Default for RegexInfo {
     fn default() -> Self {
         Self {
             title: Default::default(),
+            enabled: true,
             r#match: Err("".into()),
             match_parse_error: Default::default(),
             replace: Default::default(),
+            flags: Default::default(),
+            test_cases: Default::default(),
+            skip_if_prev_no_match: false,
+            apply_once_per_line: false,
+            match_count: Default::default(),
+            has_match_on_input: None,
+            avg_ms: None,
+            complexity_risk: None,
         }
     }
 }
@@ -41,10 +388,17 @@ impl Default for StepProps {
     fn default() -> Self {
         Self {
             title: "".into(),
+            description: "".into(),
             enabled: true,
             selected: false,
             restart_on_match: true,
             virtual_sort: VirtualSort::None,
+            run_only_if_prev_changed: false,
+            stop_on_first_match: false,
+            priority: 0,
+            pause_between_regexes_ms: None,
+            in_config_a: true,
+            in_config_b: true,
         }
     }
 }
@@ -63,3 +417,354 @@ impl Step {
         }
     }
 }
+
+/// Whether the name can be used for a named capture group.
+pub fn is_valid_group_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c == '_' || c.is_ascii_alphabetic())
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Renames the `$old` and `${old}` references of the replacement, leaving
+/// other references and escaped `$$` as they are.
+fn rename_group_references(replacement: &str, old: &str, new: &str) -> String {
+    let is_name_char = |c: char| c == '_' || c.is_ascii_alphanumeric();
+    let mut renamed = String::new();
+    let mut rest = replacement;
+    while let Some(dollar) = rest.find('$') {
+        renamed.push_str(&rest[..dollar]);
+        let after = &rest[dollar + 1..];
+        if let Some(after_escape) = after.strip_prefix('$') {
+            renamed.push_str("$$");
+            rest = after_escape;
+        } else if let Some(braced) = after.strip_prefix('{') {
+            match braced.find('}') {
+                Some(end) if &braced[..end] == old => {
+                    renamed.push_str(&format!("${{{}}}", new));
+                    rest = &braced[end + 1..];
+                }
+                _ => {
+                    renamed.push('$');
+                    rest = after;
+                }
+            }
+        } else {
+            let end = after.find(|c| !is_name_char(c)).unwrap_or(after.len());
+            if &after[..end] == old {
+                renamed.push_str(&format!("${}", new));
+            } else {
+                renamed.push('$');
+                renamed.push_str(&after[..end]);
+            }
+            rest = &after[end..];
+        }
+    }
+    renamed.push_str(rest);
+    renamed
+}
+
+/// Parses one regex match per non-empty line.
+///
+/// Lines starting with `#` are comments that become the title of the
+/// following regex. Matches starting with `(?x)` are verbose, the flag being
+/// set instead. Invalid matches are kept in their error state.
+pub fn regexes_from_text(text: &str) -> Vec<RegexInfo> {
+    let mut regexes = vec![];
+    let mut title = None;
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            title = Some(comment.trim().to_string());
+            continue;
+        }
+        let mut info = RegexInfo::new(title.take().unwrap_or_default(), "".into(), "".into());
+        match line.strip_prefix("(?x)") {
+            Some(pattern) => {
+                info.flags.verbose = true;
+                info.set_match(pattern.to_string());
+            }
+            None => info.set_match(line.to_string()),
+        }
+        regexes.push(info);
+    }
+    regexes
+}
+
+/// Parses one regex match per non-empty line, without titles nor comments.
+///
+/// Invalid matches are kept in their error state.
+pub fn regexes_from_lines(text: &str) -> Vec<RegexInfo> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| RegexInfo::new("".into(), line.to_string(), "".into()))
+        .collect()
+}
+
+/// A regex as written by other tools, such as the output of
+/// [`crate::export::step_to_tool_json`].
+#[derive(Deserialize)]
+struct JsonRegex {
+    pattern: String,
+    #[serde(default)]
+    replacement: String,
+    #[serde(default)]
+    title: String,
+    /// JavaScript-like flag letters, such as `gi`.
+    #[serde(default)]
+    flags: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonRegexes {
+    One(JsonRegex),
+    Many(Vec<JsonRegex>),
+}
+
+/// Rewrites a JavaScript pattern, where named groups are written as
+/// `(?<name>...)`, in this crate's syntax.
+fn pattern_from_js(pattern: &str) -> String {
+    let mut rust = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+    while let Some(c) = chars.next() {
+        rust.push(c);
+        match c {
+            '\\' => rust.extend(chars.next()),
+            '[' => in_class = true,
+            ']' => in_class = false,
+            // but not the lookbehinds `(?<=` and `(?<!`
+            '(' if !in_class
+                && chars.clone().take(2).eq("?<".chars())
+                && !matches!(chars.clone().nth(2), Some('=') | Some('!')) =>
+            {
+                rust.push_str("?P<");
+                chars.nth(1);
+            }
+            _ => {}
+        }
+    }
+    rust
+}
+
+/// Rewrites a JavaScript replacement, where named groups are referenced as
+/// `$<name>` and `$&` is the whole match, in this crate's syntax.
+fn replacement_from_js(replacement: &str) -> String {
+    let mut rust = String::with_capacity(replacement.len());
+    let mut rest = replacement;
+    while let Some(pos) = rest.find('$') {
+        rust.push_str(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len())
+            .min(2);
+        if digits > 0 {
+            // braced, so the text after it isn't read as part of the name
+            rust.push_str(&format!("${{{}}}", &rest[..digits]));
+            rest = &rest[digits..];
+        } else if let Some((name, after)) = rest
+            .strip_prefix('<')
+            .and_then(|named| named.split_once('>'))
+        {
+            rust.push_str(&format!("${{{}}}", name));
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('&') {
+            rust.push_str("${0}");
+            rest = after;
+        } else {
+            // either `$$` or a `$` that doesn't reference a group, which are
+            // both a literal `$`
+            rust.push_str("$$");
+            rest = rest.strip_prefix('$').unwrap_or(rest);
+        }
+    }
+    rust.push_str(rest);
+    rust
+}
+
+/// Parses a JSON object with `pattern`, and optionally `replacement`,
+/// `title` and `flags` fields, or an array of such objects.
+///
+/// The pattern and replacement are read in JavaScript syntax. Of the flags,
+/// only `i`, `m` and `s` are kept. Invalid matches are kept in their error
+/// state.
+pub fn regexes_from_json(text: &str) -> Result<Vec<RegexInfo>, String> {
+    let regexes = match serde_json::from_str(text).map_err(|err| err.to_string())? {
+        JsonRegexes::One(regex) => vec![regex],
+        JsonRegexes::Many(regexes) => regexes,
+    };
+    Ok(regexes
+        .into_iter()
+        .map(|r| {
+            let mut info = RegexInfo::new(
+                r.title,
+                pattern_from_js(&r.pattern),
+                replacement_from_js(&r.replacement),
+            );
+            if !r.flags.is_empty() {
+                info.set_flags(RegexFlags {
+                    case_insensitive: r.flags.contains('i'),
+                    multiline: r.flags.contains('m'),
+                    dot_all: r.flags.contains('s'),
+                    ..Default::default()
+                });
+            }
+            info
+        })
+        .collect())
+}
+
+/// Finds regexes with the same match as a previous regex of the same step.
+///
+/// Each pair is `(original, duplicate)`, where the duplicate is never reached
+/// because the original has a higher priority.
+pub fn find_duplicate_regexes(step: &Step) -> Vec<(RegexIndex, RegexIndex)> {
+    let mut duplicates = vec![];
+    for (j, r) in step.regexes.iter().enumerate() {
+        let re = match &r.r#match {
+            Ok(re) => re,
+            Err(_) => continue,
+        };
+        let original = step.regexes[..j]
+            .iter()
+            .position(|other| match &other.r#match {
+                Ok(other_re) => other_re.as_str() == re.as_str(),
+                Err(_) => false,
+            });
+        if let Some(original) = original {
+            duplicates.push((original, j));
+        }
+    }
+    duplicates
+}
+
+/// Expands a replacement template as if every capture group was empty.
+fn expand_without_captures(replacement: &str) -> String {
+    let mut expanded = String::new();
+    let mut chars = replacement.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            expanded.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('$') => {
+                chars.next();
+                expanded.push('$');
+            }
+            Some('{') => {
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+            }
+            Some(c) if c.is_ascii_alphanumeric() || *c == '_' => {
+                while matches!(chars.peek(), Some(c) if c.is_ascii_alphanumeric() || *c == '_') {
+                    chars.next();
+                }
+            }
+            _ => expanded.push('$'),
+        }
+    }
+    expanded
+}
+
+/// Heuristically tells whether the replacement always gives back the
+/// matched text, so the regex never changes the content.
+///
+/// That's the case when the replacement is only the whole match, `$0` or
+/// `${0}`, or when both the pattern and the replacement are the same plain
+/// literal. A case insensitive or verbose pattern matches other texts than
+/// its literal, so it's never taken as plain.
+pub fn is_noop_replacement(pattern: &str, replacement: &str, flags: RegexFlags) -> bool {
+    if pattern.is_empty() {
+        return false;
+    }
+    if matches!(replacement, "$0" | "${0}") {
+        return true;
+    }
+    if flags.case_insensitive || flags.verbose {
+        return false;
+    }
+    let is_literal = regex_syntax::escape(pattern) == pattern;
+    is_literal && !replacement.contains('$') && replacement == pattern
+}
+
+/// Heuristically finds regexes whose replacement may be matched again.
+///
+/// Each pair is `(a, b)`, where the replacement of `a`, with empty capture
+/// groups, is matched by `b`. This includes a regex re-matching its own
+/// replacement, which is a replacement cycle.
+pub fn detect_potential_conflicts(step: &Step) -> Vec<(RegexIndex, RegexIndex)> {
+    let mut conflicts = vec![];
+    for (a, r_a) in step.regexes.iter().enumerate() {
+        if r_a.r#match.is_err() {
+            continue;
+        }
+        let produced = expand_without_captures(&r_a.replace);
+        if produced.is_empty() {
+            continue;
+        }
+        for (b, r_b) in step.regexes.iter().enumerate() {
+            if let Ok(re_b) = &r_b.r#match {
+                if re_b.is_match(&produced) {
+                    conflicts.push((a, b));
+                }
+            }
+        }
+    }
+    conflicts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verbose_regexes_round_trip_through_text() {
+        let mut info = RegexInfo::new(
+            "Dates".into(),
+            "(?P<year>\\d{4}) - # year\n  (?P<month>\\d{2})  # month\n".into(),
+            "".into(),
+        );
+        info.set_flags(RegexFlags {
+            verbose: true,
+            ..Default::default()
+        });
+        let step = Step {
+            regexes: vec![info],
+            ..Default::default()
+        };
+        let regexes = regexes_from_text(&crate::export::step_to_text(&step));
+        assert_eq!(regexes.len(), 1);
+        assert_eq!(regexes[0].title, "Dates");
+        assert!(regexes[0].flags.verbose);
+        let re = regexes[0].r#match.as_ref().unwrap();
+        assert_eq!(re.as_str(), r"(?P<year>\d{4})-(?P<month>\d{2})");
+        assert!(re.is_match("2021-03"));
+    }
+
+    #[test]
+    fn pattern_from_js_renames_named_groups() {
+        assert_eq!(pattern_from_js(r"(?<year>\d{4})"), r"(?P<year>\d{4})");
+        assert_eq!(pattern_from_js(r"[(?<]"), r"[(?<]");
+    }
+
+    #[test]
+    fn pattern_from_js_keeps_lookbehinds() {
+        assert_eq!(pattern_from_js(r"(?<=a)(?<!b)c"), r"(?<=a)(?<!b)c");
+    }
+
+    #[test]
+    fn replacement_from_js_references_groups() {
+        assert_eq!(replacement_from_js("$$"), "$$");
+        assert_eq!(replacement_from_js("$<name>"), "${name}");
+        assert_eq!(replacement_from_js("$1a"), "${1}a");
+        assert_eq!(replacement_from_js("$&"), "${0}");
+        assert_eq!(replacement_from_js("$ and $"), "$$ and $$");
+    }
+}
@@ -0,0 +1,134 @@
+use crate::step::{RegexInfo, Step};
+
+/// A built-in set of steps for a common use case.
+pub struct Template {
+    pub title: &'static str,
+    pub steps: &'static [TemplateStep],
+}
+
+pub struct TemplateStep {
+    pub title: &'static str,
+    /// Regexes as `(title, match, replacement)`.
+    pub regexes: &'static [(&'static str, &'static str, &'static str)],
+}
+
+impl Template {
+    /// Creates the steps described by this template.
+    pub fn steps(&self) -> Vec<Step> {
+        self.steps
+            .iter()
+            .map(|template_step| {
+                let mut step = Step::default();
+                step.props.title = template_step.title.into();
+                step.regexes = template_step
+                    .regexes
+                    .iter()
+                    .map(|(title, search, replace)| {
+                        RegexInfo::new(title.to_string(), search.to_string(), replace.to_string())
+                    })
+                    .collect();
+                step
+            })
+            .collect()
+    }
+}
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        title: "HTML entity decode",
+        steps: &[TemplateStep {
+            title: "Decode HTML entities",
+            regexes: &[
+                ("Less than", "&lt;", "<"),
+                ("Greater than", "&gt;", ">"),
+                ("Double quote", "&quot;", "\""),
+                ("Single quote", "&#0?39;|&apos;", "'"),
+                ("Non-breaking space", "&nbsp;", " "),
+                ("Ampersand", "&amp;", "&"),
+            ],
+        }],
+    },
+    Template {
+        title: "Strip HTML tags",
+        steps: &[TemplateStep {
+            title: "Remove tags",
+            regexes: &[
+                ("Line breaks into new lines", "(?i)<br\\s*/?>", "\n"),
+                ("Any other tag", "<[^>]+>", ""),
+            ],
+        }],
+    },
+    Template {
+        title: "Remove duplicate spaces",
+        steps: &[TemplateStep {
+            title: "Collapse spaces",
+            regexes: &[("Two or more spaces", " {2,}", " ")],
+        }],
+    },
+    Template {
+        title: "Trim trailing whitespace",
+        steps: &[TemplateStep {
+            title: "Trim line ends",
+            regexes: &[("Whitespace before a line end", "(?m)[ \\t]+$", "")],
+        }],
+    },
+    Template {
+        title: "Normalize line breaks",
+        steps: &[
+            TemplateStep {
+                title: "Unix line endings",
+                regexes: &[("Windows or old Mac line ending", "\\r\\n?", "\n")],
+            },
+            TemplateStep {
+                title: "Collapse blank lines",
+                regexes: &[("Three or more line breaks", "\\n{3,}", "\n\n")],
+            },
+        ],
+    },
+    Template {
+        title: "Straighten curly quotes",
+        steps: &[TemplateStep {
+            title: "Straight quotes",
+            regexes: &[
+                ("Double quotes", "[“”„]", "\""),
+                ("Single quotes", "[‘’‚]", "'"),
+            ],
+        }],
+    },
+    Template {
+        title: "Split camelCase words",
+        steps: &[TemplateStep {
+            title: "Split words",
+            regexes: &[(
+                "Lowercase followed by uppercase",
+                "([[:lower:]])([[:upper:]])",
+                "$1 $2",
+            )],
+        }],
+    },
+    Template {
+        title: "Full-width punctuation to ASCII",
+        steps: &[
+            TemplateStep {
+                title: "Replace punctuation",
+                regexes: &[
+                    ("Comma", "[，、]", ", "),
+                    ("Full stop", "。", ". "),
+                    ("Exclamation mark", "！", "! "),
+                    ("Question mark", "？", "? "),
+                    ("Colon", "：", ": "),
+                    ("Semicolon", "；", "; "),
+                    ("Opening parenthesis", "（", " ("),
+                    ("Closing parenthesis", "）", ") "),
+                ],
+            },
+            TemplateStep {
+                title: "Tidy up spaces",
+                regexes: &[
+                    ("Two or more spaces", " {2,}", " "),
+                    ("Spaces before a line end", "(?m) +$", ""),
+                ],
+            },
+        ],
+    },
+];
@@ -1,6 +1,13 @@
+use indexmap::IndexMap;
+use yew::prelude::*;
+
 pub struct TextProjectProps {
     pub title: String,
     pub commentary: Option<String>,
+    /// Where the output is POSTed to after each finished replacement.
+    pub webhook_url: Option<String>,
+    /// Labels that the project tabs can be filtered by.
+    pub tags: Vec<String>,
 }
 
 impl Default for TextProjectProps {
@@ -8,31 +15,187 @@ impl Default for TextProjectProps {
         Self {
             title: "".into(),
             commentary: None,
+            webhook_url: None,
+            tags: vec![],
         }
     }
 }
 
-#[derive(Default)]
 pub struct TextProject {
     pub props: TextProjectProps,
     pub input: String,
     pub output: String,
     pub output_status: OutputStatus,
+    /// Time, in milliseconds, that each regex of each step spent during the
+    /// latest finished replacement.
+    pub regex_durations: Vec<Vec<f64>>,
+    /// How many bytes the latest finished replacement inserted and deleted,
+    /// from the input to the output.
+    ///
+    /// Is `None` if the texts were too large to be compared.
+    pub byte_changes: Option<(usize, usize)>,
+    /// Variables that shadow the global variables of the same name when
+    /// replacing this project's text.
+    pub local_variables: IndexMap<String, String>,
+    /// How the latest POST of the output to the webhook went.
+    pub webhook_status: Option<WebhookStatus>,
+    /// Outputs of the latest A/B comparison, from the steps of the first and
+    /// second configurations.
+    pub ab_result: Option<(String, String)>,
+    /// Where the input is fetched from, as written by the user.
+    pub input_url: String,
+    /// How the latest fetch of the input from the URL went.
+    ///
+    /// Is `None` once the fetched text replaces the input.
+    pub input_fetch_status: Option<InputFetchStatus>,
+    /// Whether long lines of the input wrap, instead of scrolling
+    /// horizontally.
+    pub wrap_input: bool,
+    /// Whether long lines of the output wrap, instead of scrolling
+    /// horizontally.
+    pub wrap_output: bool,
+    /// Whether the input and output are written right-to-left, such as
+    /// Arabic, Hebrew or Persian texts.
+    pub rtl_mode: bool,
+    /// Previous outputs, from the oldest to the most recent.
+    pub archive: Vec<ArchivedOutput>,
+    /// What happened to the project, from the oldest to the most recent.
+    pub events: Vec<ProjectEvent>,
+}
+
+/// How many events each project keeps in its activity log.
+pub const MAX_PROJECT_EVENTS: usize = 100;
+
+/// Something that happened to a project, timestamped in milliseconds since
+/// the epoch.
+pub enum ProjectEvent {
+    InputChanged { at: f64, char_count: usize },
+    ReplacementStarted { at: f64 },
+    ReplacementFinished { at: f64, duration_ms: f64 },
+    ReplacementCancelled { at: f64, motive: CancelMotive },
+}
+
+impl ProjectEvent {
+    pub fn at(&self) -> f64 {
+        match self {
+            ProjectEvent::InputChanged { at, .. }
+            | ProjectEvent::ReplacementStarted { at }
+            | ProjectEvent::ReplacementFinished { at, .. }
+            | ProjectEvent::ReplacementCancelled { at, .. } => *at,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            ProjectEvent::InputChanged { char_count, .. } => {
+                format!("The input changed, to {} characters.", char_count)
+            }
+            ProjectEvent::ReplacementStarted { .. } => "A replacement started.".to_string(),
+            ProjectEvent::ReplacementFinished { duration_ms, .. } => {
+                format!("The replacement finished in {:.0} ms.", duration_ms)
+            }
+            ProjectEvent::ReplacementCancelled { motive, .. } => {
+                let motive = match motive {
+                    CancelMotive::ManuallyCancelled => "manually",
+                    CancelMotive::CycleDetected => "due to a replacement cycle",
+                    CancelMotive::HighGrowth => "because the text was growing too much",
+                    CancelMotive::Timeout => "because it took longer than the timeout",
+                };
+                format!("The replacement was cancelled {}.", motive)
+            }
+        }
+    }
+}
+
+/// How many outputs each project keeps in its archive.
+pub const MAX_ARCHIVED_OUTPUTS: usize = 5;
+
+/// An output kept for reference after it's replaced by a newer one.
+pub struct ArchivedOutput {
+    pub output: String,
+    /// When the output was archived, in milliseconds since the epoch.
+    pub timestamp: f64,
+    /// Titles of the steps that were enabled when the output was archived.
+    pub step_snapshot: Vec<String>,
+}
+
+impl Default for TextProject {
+    fn default() -> Self {
+        Self {
+            props: Default::default(),
+            input: Default::default(),
+            output: Default::default(),
+            output_status: Default::default(),
+            regex_durations: Default::default(),
+            byte_changes: None,
+            local_variables: Default::default(),
+            webhook_status: None,
+            ab_result: None,
+            input_url: Default::default(),
+            input_fetch_status: None,
+            wrap_input: true,
+            wrap_output: true,
+            rtl_mode: false,
+            archive: vec![],
+            events: vec![],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum InputFetchStatus {
+    Loading,
+    Failed(String),
+}
+
+#[derive(Debug)]
+pub enum WebhookStatus {
+    Sending,
+    /// The webhook responded with this successful status code.
+    Sent(u16),
+    Failed(String),
 }
 
 #[derive(Debug)]
 pub enum OutputStatus {
     Outdated,
-    InProgress,
+    /// The replacement is running over `total` chunks of the input, of which
+    /// `processed` are already done.
+    ///
+    /// For the chunk being processed, `completed_steps` out of `total_steps`
+    /// are already done.
+    InProgress {
+        processed: usize,
+        total: usize,
+        completed_steps: usize,
+        total_steps: usize,
+        /// Which regex of the running step made the latest substitution,
+        /// if the step pauses after its substitutions.
+        fired_regex: Option<usize>,
+    },
     Done,
     Cancelled(CancelMotive),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub enum CancelMotive {
     ManuallyCancelled,
     CycleDetected,
     HighGrowth,
+    /// The replacement took longer than the global timeout.
+    Timeout,
+}
+
+impl CancelMotive {
+    /// Why the replacement was cancelled, in a few words.
+    pub fn description(&self) -> &'static str {
+        match self {
+            CancelMotive::ManuallyCancelled => "manually cancelled",
+            CancelMotive::CycleDetected => "cycle detected",
+            CancelMotive::HighGrowth => "the text grew too much",
+            CancelMotive::Timeout => "timed out",
+        }
+    }
 }
 
 impl Default for OutputStatus {
@@ -40,3 +203,132 @@ impl Default for OutputStatus {
         OutputStatus::Done
     }
 }
+
+impl TextProject {
+    /// Appends the event to the activity log, dropping the oldest event if
+    /// there are too many.
+    ///
+    /// Consecutive input changes are merged into the latest one, so typing
+    /// doesn't push the replacements out of the log.
+    pub fn record(&mut self, event: ProjectEvent) {
+        if let (ProjectEvent::InputChanged { .. }, Some(last @ ProjectEvent::InputChanged { .. })) =
+            (&event, self.events.last_mut())
+        {
+            *last = event;
+            return;
+        }
+        self.events.push(event);
+        if self.events.len() > MAX_PROJECT_EVENTS {
+            self.events.remove(0);
+        }
+    }
+
+    /// The time since the latest replacement started, in milliseconds.
+    pub fn replacement_duration(&self) -> Option<f64> {
+        self.events.iter().rev().find_map(|event| match event {
+            ProjectEvent::ReplacementStarted { at } => Some(js_sys::Date::now() - at),
+            _ => None,
+        })
+    }
+
+    /// Renders the activity log, from the most recent event.
+    pub fn render_events(&self) -> Html {
+        html! {
+            <ul class="is-size-7">
+            { for self.events.iter().rev().map(|event| {
+                let at = js_sys::Date::new(&event.at().into()).to_locale_time_string("default");
+                html! {
+                    <li>
+                        <span class="has-text-grey mr-2">{String::from(at)}</span>
+                        {event.description()}
+                    </li>
+                }
+            })}
+            </ul>
+        }
+    }
+
+    /// Keeps the current output in the archive, dropping the oldest archived
+    /// output if there are too many.
+    ///
+    /// Empty outputs, and outputs equal to the most recently archived one,
+    /// are not archived.
+    pub fn archive_output(&mut self, step_snapshot: Vec<String>) {
+        if self.output.is_empty()
+            || matches!(self.archive.last(), Some(latest) if latest.output == self.output)
+        {
+            return;
+        }
+        self.archive.push(ArchivedOutput {
+            output: self.output.clone(),
+            timestamp: js_sys::Date::now(),
+            step_snapshot,
+        });
+        if self.archive.len() > MAX_ARCHIVED_OUTPUTS {
+            self.archive.remove(0);
+        }
+    }
+
+    /// Renders a bar chart of how long each regex took in the latest
+    /// replacement, highlighting regexes that dominate their step's time.
+    pub fn render_regex_durations(&self) -> Html {
+        const BAR_HEIGHT: usize = 12;
+        const ROW_HEIGHT: usize = 16;
+        const BAR_MAX_WIDTH: f64 = 200.;
+
+        let steps = self
+            .regex_durations
+            .iter()
+            .enumerate()
+            .filter(|(_, durations)| !durations.is_empty());
+
+        html! {
+            <div>
+            { for steps.map(|(i, durations)| {
+                let step_total: f64 = durations.iter().sum();
+                let max = durations.iter().cloned().fold(0., f64::max);
+                let height = durations.len() * ROW_HEIGHT;
+                html! {
+                    <div class="mb-2">
+                        <p class="is-size-7">
+                            {format!("Step {} - {:.2}ms", i + 1, step_total)}
+                        </p>
+                        <svg
+                            width={(BAR_MAX_WIDTH + 100.).to_string()}
+                            height={height.to_string()}
+                        >
+                        { for durations.iter().enumerate().map(|(j, duration)| {
+                            let width = if max > 0. {
+                                duration / max * BAR_MAX_WIDTH
+                            } else {
+                                0.
+                            };
+                            let is_slow = step_total > 0. && *duration > 0.8 * step_total;
+                            let y = j * ROW_HEIGHT;
+                            html! {
+                                <g>
+                                    <rect
+                                        x="0"
+                                        y={y.to_string()}
+                                        width={width.to_string()}
+                                        height={BAR_HEIGHT.to_string()}
+                                        fill={if is_slow { "#f14668" } else { "#3e8ed0" }}
+                                    />
+                                    <text
+                                        x={(width + 4.).to_string()}
+                                        y={(y + BAR_HEIGHT - 2).to_string()}
+                                        font-size="10"
+                                    >
+                                        {format!("Regex {} - {:.2}ms", j + 1, duration)}
+                                    </text>
+                                </g>
+                            }
+                        })}
+                        </svg>
+                    </div>
+                }
+            })}
+            </div>
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use indexmap::IndexMap;
+
+/// Whether the name can be referenced as `$NAME` in a replacement.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Expands every `$NAME` or `${NAME}` of the replacement that refers to a
+/// defined variable.
+///
+/// References to undefined names are kept as-is, so they still work as
+/// capture group references. Values are escaped so they are inserted
+/// literally.
+pub fn expand_variables(replacement: &str, variables: &IndexMap<String, String>) -> String {
+    if variables.is_empty() {
+        return replacement.into();
+    }
+    let re = regex::Regex::new(r"\$\$|\$\{([A-Za-z_][A-Za-z0-9_]*)\}|\$([A-Za-z_][A-Za-z0-9_]*)")
+        .expect("variable reference regex is valid");
+    re.replace_all(replacement, |caps: &regex::Captures| {
+        let name = caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str());
+        match name.and_then(|name| variables.get(name)) {
+            Some(value) => value.replace('$', "$$"),
+            // an escaped `$` or a capture group reference
+            None => caps[0].to_string(),
+        }
+    })
+    .into_owned()
+}